@@ -0,0 +1,105 @@
+use std::future::Future;
+
+use crate::{
+    models::{
+        album::Album,
+        artist::Artist,
+        market::Market,
+        playlist::Playlist,
+        recommendations::*,
+        search::{ SearchResults, SearchType },
+        track::Track,
+    },
+    RustyResult,
+    SpotifyClientCredentials,
+};
+
+/// The core read operations of the Spotify Web API.
+///
+/// Application code generic over `impl SpotifyApi` can run against [`SpotifyClientCredentials`]
+/// in production and against a test double, such as `MockSpotifyClient` from the `mock` feature,
+/// in tests.
+///
+/// The methods return `Send` futures, so generic code can be spawned onto a multi-threaded runtime.
+///
+/// # Examples
+///
+/// ```
+/// use rustyspoty::{ RustyResult, SpotifyApi };
+///
+/// async fn describe_album(api: &impl SpotifyApi, album_id: &str) -> RustyResult<String> {
+///     let album = api.get_album(album_id).await?;
+///     Ok(format!("{} ({} tracks)", album.name, album.total_tracks))
+/// }
+/// ```
+pub trait SpotifyApi {
+    /// Fetches an album by its Spotify ID.
+    fn get_album(&self, album_id: &str) -> impl Future<Output = RustyResult<Album>> + Send;
+
+    /// Fetches an artist by its Spotify ID.
+    fn get_artist(&self, artist_id: &str) -> impl Future<Output = RustyResult<Artist>> + Send;
+
+    /// Fetches a track by its Spotify ID.
+    fn get_track(&self, track_id: &str) -> impl Future<Output = RustyResult<Track>> + Send;
+
+    /// Fetches a playlist by its Spotify ID.
+    fn get_playlist(
+        &self,
+        playlist_id: &str
+    ) -> impl Future<Output = RustyResult<Playlist>> + Send;
+
+    /// Fetches track recommendations for the given seeds and tunable attributes.
+    fn get_recommendations(
+        &self,
+        request: &RecommendationsRequest
+    ) -> impl Future<Output = RustyResult<RecommendationsResponse>> + Send;
+
+    /// Searches the catalog for items of the given types matching a keyword query.
+    fn search(
+        &self,
+        query: &str,
+        types: &[SearchType],
+        market: Option<Market>,
+        limit: Option<u32>,
+        offset: Option<u32>
+    ) -> impl Future<Output = RustyResult<SearchResults>> + Send;
+}
+
+impl SpotifyApi for SpotifyClientCredentials {
+    fn get_album(&self, album_id: &str) -> impl Future<Output = RustyResult<Album>> + Send {
+        SpotifyClientCredentials::get_album(self, album_id)
+    }
+
+    fn get_artist(&self, artist_id: &str) -> impl Future<Output = RustyResult<Artist>> + Send {
+        SpotifyClientCredentials::get_artist(self, artist_id)
+    }
+
+    fn get_track(&self, track_id: &str) -> impl Future<Output = RustyResult<Track>> + Send {
+        SpotifyClientCredentials::get_track(self, track_id)
+    }
+
+    fn get_playlist(
+        &self,
+        playlist_id: &str
+    ) -> impl Future<Output = RustyResult<Playlist>> + Send {
+        SpotifyClientCredentials::get_playlist(self, playlist_id)
+    }
+
+    fn get_recommendations(
+        &self,
+        request: &RecommendationsRequest
+    ) -> impl Future<Output = RustyResult<RecommendationsResponse>> + Send {
+        SpotifyClientCredentials::get_recommendations(self, request)
+    }
+
+    fn search(
+        &self,
+        query: &str,
+        types: &[SearchType],
+        market: Option<Market>,
+        limit: Option<u32>,
+        offset: Option<u32>
+    ) -> impl Future<Output = RustyResult<SearchResults>> + Send {
+        SpotifyClientCredentials::search(self, query, types, market, limit, offset)
+    }
+}
@@ -5,6 +5,9 @@ use reqwest::{ Client as HttpClient, Url };
 use serde::{ Deserialize, Serialize };
 use sha2::{ Digest, Sha256 };
 use std::str;
+use std::time::{ Duration, SystemTime, UNIX_EPOCH };
+
+use crate::{ RustyResult, SpotifyUserClient };
 
 /// Represents errors that might occur during the OAuth process.
 #[derive(Debug)]
@@ -33,18 +36,28 @@ impl From<base64::DecodeError> for OAuthError {
     }
 }
 
+/// The Spotify Accounts service endpoint issuing access tokens.
+const SPOTIFY_TOKEN_URL: &str = "https://accounts.spotify.com/api/token";
+
 /// Represents the OAuth client for performing the Authorization Code with PKCE Flow.
+#[derive(Clone)]
 pub struct SpotifyOAuth {
     client_id: String,
     redirect_uri: String,
     scope: String,
     code_verifier: String,
+    /// The random `state` sent with the authorization request, returned by Spotify to the
+    /// redirect URI to protect against cross-site request forgery.
+    state: String,
     http_client: HttpClient,
+    /// The token endpoint, overridden in tests to target a mock server.
+    token_url: String,
 }
 
 impl SpotifyOAuth {
     pub fn new(client_id: String, redirect_uri: String, scope: String) -> Self {
         let code_verifier = Self::generate_code_verifier();
+        let state = Self::generate_state();
         let http_client = HttpClient::new();
 
         SpotifyOAuth {
@@ -52,23 +65,72 @@ impl SpotifyOAuth {
             redirect_uri,
             scope,
             code_verifier,
+            state,
             http_client,
+            token_url: SPOTIFY_TOKEN_URL.to_string(),
         }
     }
 
+    /// The `state` sent with the authorization request.
+    ///
+    /// Spotify passes it back to the redirect URI in the `state` query parameter. Check that it
+    /// matches with [`verify_state`](Self::verify_state) before exchanging the code, and keep the
+    /// `SpotifyOAuth` (or this value) in the user's session until the callback.
+    pub fn state(&self) -> &str {
+        &self.state
+    }
+
+    /// Checks that the `state` passed to the redirect URI is the one sent with the authorization
+    /// request, rejecting callbacks the user did not initiate.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The `state` query parameter of the callback.
+    pub fn verify_state(&self, state: &str) -> bool {
+        // Compares every byte, so the time taken does not reveal how much of the state matched
+        state.len() == self.state.len() &&
+            state
+                .bytes()
+                .zip(self.state.bytes())
+                .fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+    }
+
+    /// The Spotify client ID the user authorizes.
+    pub(crate) fn client_id(&self) -> &str {
+        &self.client_id
+    }
+
+    /// The HTTP client used to reach the Spotify Accounts service.
+    pub(crate) fn http_client(&self) -> &HttpClient {
+        &self.http_client
+    }
+
+    /// Points the OAuth client at another token endpoint, such as a mock server.
+    #[cfg(test)]
+    pub(crate) fn set_token_url(&mut self, token_url: String) {
+        self.token_url = token_url;
+    }
+
     /// Generates a code verifier for the PKCE flow.
     fn generate_code_verifier() -> String {
         rand::thread_rng().sample_iter(&Alphanumeric).take(128).map(char::from).collect()
     }
 
+    /// Generates the `state` of the authorization request.
+    fn generate_state() -> String {
+        rand::thread_rng().sample_iter(&Alphanumeric).take(32).map(char::from).collect()
+    }
+
     /// Generates the code challenge from the code verifier using SHA256 and base64 URL-safe encoding without padding.
     fn generate_code_challenge(&self) -> Result<String, OAuthError> {
         let digest = Sha256::digest(self.code_verifier.as_bytes());
-        let encoded = URL_SAFE_NO_PAD.encode(&digest);
+        let encoded = URL_SAFE_NO_PAD.encode(digest);
         Ok(encoded)
     }
 
     /// Constructs the authorization URL to which the user should be redirected.
+    ///
+    /// The URL carries the [`state`](Self::state) of this OAuth client.
     pub async fn get_authorize_url(&self) -> Result<String, OAuthError> {
         let code_challenge = self.generate_code_challenge()?;
         let mut auth_url: Url = Url::parse("https://accounts.spotify.com/authorize")?;
@@ -79,17 +141,71 @@ impl SpotifyOAuth {
             .append_pair("redirect_uri", &self.redirect_uri)
             .append_pair("scope", &self.scope)
             .append_pair("code_challenge_method", "S256")
-            .append_pair("code_challenge", &code_challenge);
+            .append_pair("code_challenge", &code_challenge)
+            .append_pair("state", &self.state);
 
         Ok(auth_url.to_string())
     }
 
+    /// Extracts the authorization code and the state from the URL Spotify redirected the user to.
+    ///
+    /// # Arguments
+    ///
+    /// * `redirect_url` - The full redirect URL, e.g.
+    ///   `http://localhost:8888/callback?code=...&state=...`.
+    ///
+    /// # Returns
+    ///
+    /// The `code` to pass to [`request_access_token`](Self::request_access_token), and the
+    /// `state` if Spotify returned one. Check the state with [`verify_state`](Self::verify_state).
+    ///
+    /// # Errors
+    ///
+    /// Returns `OAuthError::UrlParseError` if `redirect_url` is not a valid URL, and
+    /// `OAuthError::Other` if the user denied the authorization (`error=access_denied`) or the
+    /// URL has no `code`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rustyspoty::SpotifyOAuth;
+    /// # let oauth = SpotifyOAuth::new("client_id".to_string(), "http://localhost:8888/callback".to_string(), "user-read-private".to_string());
+    /// let (code, state) = oauth
+    ///     .parse_redirect("http://localhost:8888/callback?code=AQD1&state=xyz")
+    ///     .unwrap();
+    /// assert_eq!(code, "AQD1");
+    /// assert_eq!(state.as_deref(), Some("xyz"));
+    /// ```
+    pub fn parse_redirect(
+        &self,
+        redirect_url: &str
+    ) -> Result<(String, Option<String>), OAuthError> {
+        let url = Url::parse(redirect_url)?;
+        let param = |name: &str| {
+            url.query_pairs()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.into_owned())
+        };
+
+        if let Some(error) = param("error") {
+            return Err(OAuthError::Other(format!("the authorization failed: {error}")));
+        }
+        let code = param("code").ok_or_else(|| {
+            OAuthError::Other(format!("the redirect URL has no authorization code: {redirect_url}"))
+        })?;
+        Ok((code, param("state")))
+    }
+
     /// Exchanges the authorization code for an access token.
+    ///
+    /// # Errors
+    ///
+    /// Returns `OAuthError::HttpError` if the request fails, and `OAuthError::Other` with the
+    /// response body if Spotify rejects the code.
     pub async fn request_access_token(
         &self,
         code: &str
     ) -> Result<AccessTokenResponse, OAuthError> {
-        let token_url = "https://accounts.spotify.com/api/token";
         let params = [
             ("client_id", self.client_id.as_str()), // Convert String to &str
             ("grant_type", "authorization_code"),
@@ -98,22 +214,252 @@ impl SpotifyOAuth {
             ("code_verifier", self.code_verifier.as_str()), // Convert String to &str
         ];
 
-        let response = self.http_client
-            .post(token_url)
-            .form(&params)
-            .send().await?
-            .json::<AccessTokenResponse>().await?;
+        let response = self.http_client.post(&self.token_url).form(&params).send().await?;
+        if !response.status().is_success() {
+            let error_message = response
+                .text().await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(OAuthError::Other(error_message));
+        }
+
+        Ok(response.json::<AccessTokenResponse>().await?)
+    }
+
+    /// Exchanges the authorization code for an access token, and creates a client authorized on
+    /// behalf of the user.
+    ///
+    /// The client refreshes its access token through this OAuth client once it expires.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - The authorization code Spotify passed to the redirect URI.
+    ///
+    /// # Errors
+    ///
+    /// Returns a network error such as `RustyError::Connect` if the token request fails, and
+    /// `RustyError::TokenAuthentication` if Spotify rejects the code.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rustyspoty::{ RustyResult, SpotifyOAuth };
+    /// # async fn run(oauth: SpotifyOAuth, code: &str) -> RustyResult<()> {
+    /// let user_client = oauth.exchange_code_into_client(code).await?;
+    /// let album = user_client.get_album("4aawyAB9vmqN3uQ7FjRGTy").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn exchange_code_into_client(&self, code: &str) -> RustyResult<SpotifyUserClient> {
+        let token = self.request_access_token(code).await?;
+        Ok(SpotifyUserClient::new(self.clone(), token))
+    }
+
+    /// Exchanges a refresh token for a new access token.
+    ///
+    /// Spotify only returns a refresh token when it rotated it, otherwise `refresh_token` stays
+    /// valid and is returned in its place, so the response can always be stored as is.
+    ///
+    /// # Arguments
+    ///
+    /// * `refresh_token` - The refresh token of a previous [`AccessTokenResponse`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `OAuthError::HttpError` if the request fails, and `OAuthError::Other` with the
+    /// response body if Spotify rejects the refresh token.
+    pub async fn refresh_access_token(
+        &self,
+        refresh_token: &str
+    ) -> Result<AccessTokenResponse, OAuthError> {
+        let params = [
+            ("client_id", self.client_id.as_str()),
+            ("grant_type", "refresh_token"),
+            ("refresh_token", refresh_token),
+        ];
+
+        let response = self.http_client.post(&self.token_url).form(&params).send().await?;
+        if !response.status().is_success() {
+            let error_message = response
+                .text().await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(OAuthError::Other(error_message));
+        }
 
-        Ok(response)
+        let mut token = response.json::<AccessTokenResponse>().await?;
+        token.refresh_token.get_or_insert_with(|| refresh_token.to_string());
+        Ok(token)
     }
 }
 
 /// Represents the response from Spotify after exchanging an authorization code for an access token.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccessTokenResponse {
-    access_token: String,
-    token_type: String,
-    scope: String,
-    expires_in: usize,
-    refresh_token: Option<String>,
+    pub access_token: String,
+    pub token_type: String,
+    pub scope: String,
+    pub expires_in: usize,
+    pub refresh_token: Option<String>,
+}
+
+impl AccessTokenResponse {
+    /// Returns when the access token expires, assuming the response was just received.
+    ///
+    /// Call this right after obtaining the token, e.g. to store the expiry next to it, as
+    /// `expires_in` is relative to when Spotify issued the token.
+    pub fn expires_at(&self) -> SystemTime {
+        SystemTime::now() + Duration::from_secs(self.expires_in as u64)
+    }
+
+    /// Returns when the access token expires as a UNIX timestamp in seconds, assuming the
+    /// response was just received.
+    pub fn expires_at_unix(&self) -> u64 {
+        self.expires_at()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since_epoch| since_epoch.as_secs())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::{ matchers::{ body_string_contains, method }, Mock, MockServer, ResponseTemplate };
+
+    async fn oauth_with_token_response(token: serde_json::Value) -> (SpotifyOAuth, MockServer) {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_string_contains("grant_type=refresh_token"))
+            .and(body_string_contains("refresh_token=old_refresh_token"))
+            .and(body_string_contains("client_id=client_id"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(token))
+            .expect(1)
+            .mount(&server).await;
+
+        let mut oauth = SpotifyOAuth::new(
+            "client_id".to_string(),
+            "http://localhost:8888/callback".to_string(),
+            "user-read-private".to_string()
+        );
+        oauth.set_token_url(format!("{}/api/token", server.uri()));
+        (oauth, server)
+    }
+
+    #[tokio::test]
+    async fn test_authorize_url_carries_the_state() {
+        let oauth = SpotifyOAuth::new(
+            "client_id".to_string(),
+            "http://localhost:8888/callback".to_string(),
+            "user-read-private".to_string()
+        );
+
+        let url = Url::parse(&oauth.get_authorize_url().await.unwrap()).unwrap();
+        let state = url
+            .query_pairs()
+            .find(|(key, _)| key == "state")
+            .map(|(_, value)| value.into_owned())
+            .unwrap();
+
+        assert_eq!(state.len(), 32);
+        assert!(oauth.verify_state(&state));
+        assert!(!oauth.verify_state("forged"));
+        assert!(!oauth.verify_state(&state[..31]));
+    }
+
+    #[test]
+    fn test_parse_redirect() {
+        let oauth = SpotifyOAuth::new(
+            "client_id".to_string(),
+            "http://localhost:8888/callback".to_string(),
+            "user-read-private".to_string()
+        );
+
+        let (code, state) = oauth
+            .parse_redirect("http://localhost:8888/callback?code=AQD%2B1&state=xyz")
+            .unwrap();
+        assert_eq!(code, "AQD+1");
+        assert_eq!(state.as_deref(), Some("xyz"));
+
+        let (_, state) = oauth.parse_redirect("http://localhost:8888/callback?code=AQD1").unwrap();
+        assert!(state.is_none());
+
+        let denied = oauth.parse_redirect(
+            "http://localhost:8888/callback?error=access_denied&state=xyz"
+        );
+        assert!(matches!(denied, Err(OAuthError::Other(e)) if e.contains("access_denied")));
+        assert!(matches!(oauth.parse_redirect("http://localhost:8888/callback"), Err(OAuthError::Other(_))));
+        assert!(matches!(oauth.parse_redirect("not a url"), Err(OAuthError::UrlParseError(_))));
+    }
+
+    #[test]
+    fn test_expires_at_adds_expires_in_to_now() {
+        let token = AccessTokenResponse {
+            access_token: "access_token".to_string(),
+            token_type: "Bearer".to_string(),
+            scope: "user-read-private".to_string(),
+            expires_in: 3600,
+            refresh_token: None,
+        };
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+
+        let expires_at = token.expires_at_unix();
+        assert!((now + 3600..=now + 3601).contains(&expires_at));
+    }
+
+    #[tokio::test]
+    async fn test_refresh_access_token_keeps_the_refresh_token_when_not_rotated() {
+        let (oauth, _server) = oauth_with_token_response(
+            serde_json::json!({
+                "access_token": "new_access_token",
+                "token_type": "Bearer",
+                "scope": "user-read-private",
+                "expires_in": 3600
+            })
+        ).await;
+
+        let token = oauth.refresh_access_token("old_refresh_token").await.unwrap();
+        assert_eq!(token.access_token, "new_access_token");
+        assert_eq!(token.refresh_token.as_deref(), Some("old_refresh_token"));
+    }
+
+    #[tokio::test]
+    async fn test_request_access_token_rejected_code() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_string_contains("grant_type=authorization_code"))
+            .and(body_string_contains("code=expired_code"))
+            .respond_with(
+                ResponseTemplate::new(400).set_body_json(
+                    serde_json::json!({
+                        "error": "invalid_grant",
+                        "error_description": "Invalid authorization code"
+                    })
+                )
+            )
+            .expect(1)
+            .mount(&server).await;
+        let mut oauth = SpotifyOAuth::new(
+            "client_id".to_string(),
+            "http://localhost:8888/callback".to_string(),
+            "user-read-private".to_string()
+        );
+        oauth.set_token_url(format!("{}/api/token", server.uri()));
+
+        let result = oauth.request_access_token("expired_code").await;
+        assert!(matches!(result, Err(OAuthError::Other(e)) if e.contains("invalid_grant")));
+    }
+
+    #[tokio::test]
+    async fn test_refresh_access_token_returns_the_rotated_refresh_token() {
+        let (oauth, _server) = oauth_with_token_response(
+            serde_json::json!({
+                "access_token": "new_access_token",
+                "token_type": "Bearer",
+                "scope": "user-read-private",
+                "expires_in": 3600,
+                "refresh_token": "new_refresh_token"
+            })
+        ).await;
+
+        let token = oauth.refresh_access_token("old_refresh_token").await.unwrap();
+        assert_eq!(token.refresh_token.as_deref(), Some("new_refresh_token"));
+    }
 }
@@ -0,0 +1,44 @@
+use std::collections::HashMap;
+
+/// The outcome of a batch request, telling the resolved ids from those Spotify had no data for.
+#[derive(Debug, Clone)]
+pub struct BatchResult<T> {
+    /// The resolved items, keyed by the requested Spotify id.
+    pub found: HashMap<String, T>,
+    /// The requested ids Spotify returned no data for, in request order and without duplicates.
+    pub missing: Vec<String>,
+}
+
+impl<T> BatchResult<T> {
+    /// Splits `requested_ids` into the ones resolved in `found` and the missing ones.
+    pub(crate) fn new(requested_ids: &[String], found: HashMap<String, T>) -> Self {
+        let mut missing: Vec<String> = Vec::new();
+        for id in requested_ids {
+            if !found.contains_key(id) && !missing.contains(id) {
+                missing.push(id.clone());
+            }
+        }
+        BatchResult { found, missing }
+    }
+
+    /// Whether every requested id was resolved.
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_ids_keep_request_order() {
+        let ids = ["a", "unknown2", "b", "unknown1", "unknown2"].map(String::from);
+        let found = HashMap::from([("a".to_string(), 1), ("b".to_string(), 2)]);
+
+        let result = BatchResult::new(&ids, found);
+        assert_eq!(result.missing, ["unknown2", "unknown1"]);
+        assert_eq!(result.found.len(), 2);
+        assert!(!result.is_complete());
+    }
+}
@@ -1,37 +1,140 @@
 use std::collections::HashMap;
-use std::sync::Mutex;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{ atomic::{ AtomicU64, Ordering }, Arc, Mutex, MutexGuard };
 use std::time::{ Duration, Instant };
 
+use serde_json::Value;
+
+/// The source of the current time of a [`Cache`], replaceable in tests to control expiry.
+pub(crate) trait Clock: Send + Sync {
+    /// Returns the current `Instant`.
+    fn now(&self) -> Instant;
+}
+
+/// The [`Clock`] reading the system's monotonic clock.
+struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
 /// A cache entry that stores a value and its expiration timestamp.
 struct CacheEntry<T> {
     /// The stored value of generic type `T`.
     value: T,
     /// The `Instant` when this entry is considered expired and should no longer be returned by the cache.
     expires_at: Instant,
+    /// The `Instant` when this entry was stored.
+    stored_at: Instant,
+    /// The value of the cache's access counter when this entry was last stored or read, used to
+    /// find the least recently used entry.
+    last_access: u64,
+}
+
+/// A value returned by the client along with where it came from.
+///
+/// Useful to show a "last updated" time in a UI, or to assert cache behavior in tests.
+#[derive(Debug, Clone)]
+pub struct Cached<T> {
+    /// The returned value.
+    pub value: T,
+    /// `true` if the value was served from the cache rather than fetched from the Spotify API.
+    pub from_cache: bool,
+    /// The `Instant` the value was fetched from the Spotify API.
+    pub fetched_at: Instant,
+}
+
+/// The validators of a previously fetched response, sent back to Spotify to only download the
+/// resource again if it changed.
+///
+/// Store the validators returned in [`Conditional::Modified`] next to the value, e.g. in the
+/// application's database, and pass them to the next conditional request.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Validators {
+    /// The `ETag` of the response, sent back in `If-None-Match`.
+    pub etag: Option<String>,
+    /// The `Last-Modified` date of the response, an HTTP date such as
+    /// `Wed, 21 Oct 2015 07:28:00 GMT`, sent back in `If-Modified-Since`.
+    pub last_modified: Option<String>,
+}
+
+/// The result of a conditional request.
+#[derive(Debug, Clone)]
+pub enum Conditional<T> {
+    /// The resource did not change since the validators were obtained (`304 Not Modified`).
+    NotModified,
+    /// The resource changed, or the server ignored the validators, and was downloaded again
+    /// along with its new validators.
+    Modified(T, Validators),
 }
 
 /// A thread-safe, generic cache for storing values associated with string keys.
-/// Values in the cache have a default time-to-live (TTL) after which they are considered expired.
+/// Each value is stored with a time-to-live (TTL) after which it is considered expired.
+///
+/// The cache may be bounded to a maximum number of entries. When inserting a new key into a full
+/// cache, the expired entries are purged first, and only if the cache is still full is the least
+/// recently used entry evicted. An unexpired entry is therefore never evicted while an expired
+/// one could make room instead.
 pub struct Cache<T> {
     /// A map from string keys to cache entries, wrapped in a Mutex for thread safety.
     entries: Mutex<HashMap<String, CacheEntry<T>>>,
-    /// The default TTL for new cache entries.
-    default_ttl: Duration,
+    /// The most entries the cache holds, or `None` for an unbounded cache.
+    max_entries: Option<usize>,
+    /// Counts the reads and writes of entries, ordering them from least to most recently used.
+    access_counter: AtomicU64,
+    /// The source of the current time.
+    clock: Arc<dyn Clock>,
 }
 
 impl<T> Cache<T> {
-    /// Creates a new cache with the given default TTL for its entries.
-    ///
-    /// # Arguments
-    ///
-    /// * `default_ttl` - A `Duration` representing the default time-to-live for cache entries.
-    pub fn new(default_ttl: Duration) -> Self {
+    /// Creates a new, unbounded cache.
+    pub fn new() -> Self {
         Cache {
             entries: Mutex::new(HashMap::new()),
-            default_ttl,
+            max_entries: None,
+            access_counter: AtomicU64::new(0),
+            clock: Arc::new(SystemClock),
         }
     }
 
+    /// Bounds the cache to `max_entries` entries, evicting the least recently used entry when a
+    /// new key is inserted into a full cache.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_entries` - The most entries the cache holds, at least 1.
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = Some(max_entries.max(1));
+        self
+    }
+
+    /// Reads the time from `clock` instead of the system clock.
+    #[cfg(test)]
+    pub(crate) fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Returns the next value of the access counter.
+    fn next_access(&self) -> u64 {
+        self.access_counter.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Locks the entries, recovering them if a thread panicked while holding the lock.
+    ///
+    /// A panic cannot leave the map half-updated, as every operation on it is a single `HashMap`
+    /// call, so the entries of a poisoned lock are still consistent and the cache keeps working.
+    fn entries(&self) -> MutexGuard<'_, HashMap<String, CacheEntry<T>>> {
+        self.entries.lock().unwrap_or_else(|poisoned| {
+            log::warn!("recovering the cache after a thread panicked while holding its lock");
+            self.entries.clear_poison();
+            poisoned.into_inner()
+        })
+    }
+
     /// Retrieves a value from the cache by its key, if it exists and has not expired.
     ///
     /// # Arguments
@@ -44,7 +147,7 @@ impl<T> Cache<T> {
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```ignore
     /// // Assume `cache` is an instance of `Cache<String>`.
     /// if let Some(value) = cache.get("my_key") {
     ///     println!("Found value: {}", value);
@@ -53,31 +156,431 @@ impl<T> Cache<T> {
     /// }
     /// ```
     pub fn get(&self, key: &str) -> Option<T> where T: Clone {
-        let entries_lock = self.entries.lock().unwrap();
-        entries_lock.get(key).and_then(|entry| {
-            if Instant::now() < entry.expires_at { Some(entry.value.clone()) } else { None }
+        self.get_with_stored_at(key).map(|(value, _)| value)
+    }
+
+    /// Retrieves a value from the cache by its key along with the `Instant` it was stored,
+    /// if it exists and has not expired.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A string slice representing the key of the cache entry to retrieve.
+    pub fn get_with_stored_at(&self, key: &str) -> Option<(T, Instant)> where T: Clone {
+        let mut entries_lock = self.entries();
+        let now = self.clock.now();
+        entries_lock.get_mut(key).and_then(|entry| {
+            if now < entry.expires_at {
+                entry.last_access = self.next_access();
+                Some((entry.value.clone(), entry.stored_at))
+            } else {
+                None
+            }
         })
     }
 
-    /// Inserts a value into the cache with the specified key and the default TTL.
+    /// Inserts a value into the cache with the specified key and TTL.
     ///
     /// # Arguments
     ///
     /// * `key` - A string representing the key under which to store the value.
     /// * `value` - The value to store in the cache.
+    /// * `ttl` - A `Duration` representing how long this entry stays valid.
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```ignore
     /// // Assume `cache` is an instance of `Cache<String>`.
-    /// cache.set("my_key".to_string(), "my_value".to_string());
+    /// cache.set_with_ttl("my_key".to_string(), "my_value".to_string(), Duration::from_secs(5));
     /// ```
-    pub fn set(&self, key: String, value: T) {
-        let mut entries_lock = self.entries.lock().unwrap();
+    pub fn set_with_ttl(&self, key: String, value: T, ttl: Duration) {
+        let mut entries_lock = self.entries();
+        let now = self.clock.now();
+        if let Some(max_entries) = self.max_entries {
+            if !entries_lock.contains_key(&key) && entries_lock.len() >= max_entries {
+                // Expired entries make room first, so that no live entry is evicted in vain
+                entries_lock.retain(|_, entry| now < entry.expires_at);
+                while entries_lock.len() >= max_entries {
+                    let least_recently_used = entries_lock
+                        .iter()
+                        .min_by_key(|(_, entry)| entry.last_access)
+                        .map(|(key, _)| key.clone());
+                    match least_recently_used {
+                        Some(lru_key) => entries_lock.remove(&lru_key),
+                        None => break,
+                    };
+                }
+            }
+        }
         let entry = CacheEntry {
             value,
-            expires_at: Instant::now() + self.default_ttl,
+            expires_at: now + ttl,
+            stored_at: now,
+            last_access: self.next_access(),
         };
         entries_lock.insert(key, entry);
     }
+
+    /// Checks whether the cache holds an unexpired value for `key`.
+    ///
+    /// This is not considered an access of the entry, so it does not affect which entries are
+    /// evicted first.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A string slice representing the key of the cache entry to look for.
+    pub fn contains_key(&self, key: &str) -> bool {
+        let entries_lock = self.entries();
+        entries_lock.get(key).is_some_and(|entry| self.clock.now() < entry.expires_at)
+    }
+
+    /// Removes the expired entries from the cache.
+    ///
+    /// Expired entries are never returned, but stay in memory until they are overwritten or
+    /// removed by this method.
+    ///
+    /// # Returns
+    ///
+    /// The number of entries removed.
+    pub fn remove_expired(&self) -> usize {
+        let mut entries_lock = self.entries();
+        let len_before = entries_lock.len();
+        let now = self.clock.now();
+        entries_lock.retain(|_, entry| now < entry.expires_at);
+        len_before - entries_lock.len()
+    }
+
+    /// Removes the entry of `key`, expired or not.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the cache held an entry for `key`.
+    pub fn remove(&self, key: &str) -> bool {
+        self.entries().remove(key).is_some()
+    }
+
+    /// Removes every entry from the cache.
+    pub fn clear(&self) {
+        self.entries().clear();
+    }
+
+    /// Removes the entries whose key matches `predicate`, expired or not.
+    ///
+    /// # Returns
+    ///
+    /// The number of entries removed.
+    pub fn remove_matching(&self, predicate: impl Fn(&str) -> bool) -> usize {
+        let mut entries_lock = self.entries();
+        let len_before = entries_lock.len();
+        entries_lock.retain(|key, _| !predicate(key));
+        len_before - entries_lock.len()
+    }
+
+    /// Returns the number of entries stored in the cache, including expired entries that have
+    /// not been removed yet.
+    pub fn len(&self) -> usize {
+        self.entries().len()
+    }
+}
+
+impl<T> Default for Cache<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The store a client caches the responses of the Spotify Web API in.
+///
+/// Clients cache responses in memory by default. Implement this trait to share a cache between
+/// several instances of an application, e.g. behind a load balancer, with a store such as Redis,
+/// and pass it to [`with_cache_backend`](crate::SpotifyClientCredentialsBuilder::with_cache_backend).
+///
+/// Responses are stored as JSON, keyed by their request path such as `/albums/{id}`. The methods
+/// are called while a request is in flight, so they should be quick; a store that is
+/// unavailable can report a miss from `get` and ignore `set`.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use rustyspoty::CacheBackend;
+/// use serde_json::Value;
+///
+/// struct RedisCache {
+///     // e.g. a Redis connection pool
+/// }
+///
+/// impl CacheBackend for RedisCache {
+///     fn get(&self, key: &str) -> Option<Value> {
+///         // GET the key and parse the stored JSON
+///         None
+///     }
+///
+///     fn set(&self, key: String, value: Value, ttl: Duration) {
+///         // SET the key to the serialized JSON, with an expiry of `ttl`
+///     }
+///
+///     fn remove(&self, key: &str) {
+///         // DEL the key
+///     }
+///
+///     fn clear(&self) {
+///         // DEL the keys of this application
+///     }
+/// }
+/// ```
+pub trait CacheBackend: Send + Sync {
+    /// Returns the value stored under `key`, unless it is missing or expired.
+    fn get(&self, key: &str) -> Option<Value>;
+
+    /// Stores `value` under `key` for `ttl`, replacing any previous value.
+    fn set(&self, key: String, value: Value, ttl: Duration);
+
+    /// Removes the value stored under `key`, if any.
+    fn remove(&self, key: &str);
+
+    /// Removes every value.
+    fn clear(&self);
+
+    /// Returns the value stored under `key` along with the `Instant` it was stored.
+    ///
+    /// Backends that do not record when values were stored report the current time.
+    fn get_with_stored_at(&self, key: &str) -> Option<(Value, Instant)> {
+        self.get(key).map(|value| (value, Instant::now()))
+    }
+
+    /// Checks whether a value is stored under `key`, without counting as an access.
+    fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Removes the values whose key matches `predicate`, returning how many were removed.
+    ///
+    /// Backends that cannot list their keys remove nothing and return 0, leaving the values to
+    /// expire on their own.
+    fn remove_matching(&self, _predicate: &dyn Fn(&str) -> bool) -> usize {
+        0
+    }
+
+    /// Removes the expired values, returning how many were removed.
+    ///
+    /// Backends that expire values on their own have nothing to remove and return 0.
+    fn remove_expired(&self) -> usize {
+        0
+    }
+
+    /// Returns the number of values stored, or 0 for backends that cannot count them.
+    fn len(&self) -> usize {
+        0
+    }
+
+    /// Checks whether no values are stored, according to [`len`](CacheBackend::len).
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Persists the values that are not stored durably yet, called when the client shuts down.
+    ///
+    /// Does nothing by default.
+    fn flush(&self) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        Box::pin(async {})
+    }
+}
+
+impl CacheBackend for Cache<Value> {
+    fn get(&self, key: &str) -> Option<Value> {
+        Cache::get(self, key)
+    }
+
+    fn set(&self, key: String, value: Value, ttl: Duration) {
+        self.set_with_ttl(key, value, ttl);
+    }
+
+    fn remove(&self, key: &str) {
+        Cache::remove(self, key);
+    }
+
+    fn clear(&self) {
+        Cache::clear(self);
+    }
+
+    fn get_with_stored_at(&self, key: &str) -> Option<(Value, Instant)> {
+        Cache::get_with_stored_at(self, key)
+    }
+
+    fn contains_key(&self, key: &str) -> bool {
+        Cache::contains_key(self, key)
+    }
+
+    fn remove_matching(&self, predicate: &dyn Fn(&str) -> bool) -> usize {
+        Cache::remove_matching(self, predicate)
+    }
+
+    fn remove_expired(&self) -> usize {
+        Cache::remove_expired(self)
+    }
+
+    fn len(&self) -> usize {
+        Cache::len(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{ panic, thread };
+
+    const TTL: Duration = Duration::from_secs(60);
+
+    /// A [`Clock`] that only moves forward when told to.
+    struct ManualClock {
+        now: Mutex<Instant>,
+    }
+
+    impl ManualClock {
+        fn new() -> Arc<Self> {
+            Arc::new(ManualClock { now: Mutex::new(Instant::now()) })
+        }
+
+        fn advance(&self, duration: Duration) {
+            *self.now.lock().unwrap() += duration;
+        }
+    }
+
+    impl Clock for ManualClock {
+        fn now(&self) -> Instant {
+            *self.now.lock().unwrap()
+        }
+    }
+
+    fn bounded_cache(clock: &Arc<ManualClock>, max_entries: usize) -> Cache<i32> {
+        Cache::new()
+            .with_max_entries(max_entries)
+            .with_clock(Arc::clone(clock) as Arc<dyn Clock>)
+    }
+
+    #[test]
+    fn test_expired_entries_are_purged_before_evicting() {
+        let clock = ManualClock::new();
+        let cache = bounded_cache(&clock, 2);
+        cache.set_with_ttl("short".to_string(), 1, Duration::from_secs(10));
+        cache.set_with_ttl("long".to_string(), 2, TTL);
+        // `long` is now the least recently used entry, but `short` is about to expire
+        assert_eq!(cache.get("short"), Some(1));
+        clock.advance(Duration::from_secs(20));
+
+        cache.set_with_ttl("new".to_string(), 3, TTL);
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get("long"), Some(2));
+        assert_eq!(cache.get("new"), Some(3));
+        assert_eq!(cache.get("short"), None);
+    }
+
+    #[test]
+    fn test_least_recently_used_entry_is_evicted_before_expiring() {
+        let clock = ManualClock::new();
+        let cache = bounded_cache(&clock, 2);
+        cache.set_with_ttl("first".to_string(), 1, TTL);
+        clock.advance(Duration::from_secs(1));
+        cache.set_with_ttl("second".to_string(), 2, TTL);
+
+        cache.set_with_ttl("third".to_string(), 3, TTL);
+
+        assert_eq!(cache.len(), 2);
+        assert!(!cache.contains_key("first"));
+        assert!(cache.contains_key("second"));
+        assert!(cache.contains_key("third"));
+    }
+
+    #[test]
+    fn test_reading_an_entry_protects_it_from_eviction() {
+        let clock = ManualClock::new();
+        let cache = bounded_cache(&clock, 2);
+        cache.set_with_ttl("first".to_string(), 1, TTL);
+        cache.set_with_ttl("second".to_string(), 2, TTL);
+        assert_eq!(cache.get("first"), Some(1));
+        // Checking for a key is not an access, so `second` stays the least recently used
+        assert!(cache.contains_key("second"));
+
+        cache.set_with_ttl("third".to_string(), 3, TTL);
+
+        assert!(cache.contains_key("first"));
+        assert!(!cache.contains_key("second"));
+        assert!(cache.contains_key("third"));
+    }
+
+    #[test]
+    fn test_overwriting_a_key_of_a_full_cache_evicts_nothing() {
+        let clock = ManualClock::new();
+        let cache = bounded_cache(&clock, 2);
+        cache.set_with_ttl("first".to_string(), 1, TTL);
+        cache.set_with_ttl("second".to_string(), 2, TTL);
+
+        cache.set_with_ttl("first".to_string(), 10, TTL);
+
+        assert_eq!(cache.get("first"), Some(10));
+        assert_eq!(cache.get("second"), Some(2));
+    }
+
+    #[test]
+    fn test_short_ttl_entry_expires_before_long_ttl_entry() {
+        let clock = ManualClock::new();
+        let cache = Cache::new().with_clock(
+            Arc::clone(&clock) as Arc<dyn Clock>
+        );
+        cache.set_with_ttl("new-releases".to_string(), 1, Duration::from_secs(5));
+        cache.set_with_ttl("markets".to_string(), 2, Duration::from_secs(3600));
+        cache.set_with_ttl("default".to_string(), 3, TTL);
+
+        clock.advance(Duration::from_secs(10));
+
+        assert_eq!(cache.get("new-releases"), None);
+        assert_eq!(cache.get("markets"), Some(2));
+        assert_eq!(cache.get("default"), Some(3));
+    }
+
+    #[test]
+    fn test_remove_expired() {
+        let cache = Cache::new();
+        cache.set_with_ttl("fresh".to_string(), 1, TTL);
+        cache.set_with_ttl("expired".to_string(), 2, Duration::ZERO);
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.remove_expired(), 1);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get("fresh"), Some(1));
+    }
+
+    #[test]
+    fn test_remove_and_clear() {
+        let cache = Cache::new();
+        cache.set_with_ttl("first".to_string(), 1, TTL);
+        cache.set_with_ttl("second".to_string(), 2, TTL);
+
+        assert!(cache.remove("first"));
+        assert!(!cache.remove("first"));
+        assert_eq!(cache.get("second"), Some(2));
+
+        cache.clear();
+        assert_eq!(cache.len(), 0);
+    }
+
+    #[test]
+    fn test_poisoned_cache_keeps_working() {
+        let cache = Arc::new(Cache::new());
+        cache.set_with_ttl("before".to_string(), 1, TTL);
+
+        let poisoning_cache = Arc::clone(&cache);
+        let result = thread::spawn(move || {
+            let _entries = poisoning_cache.entries.lock().unwrap();
+            panic::panic_any("panic while holding the cache lock");
+        }).join();
+        assert!(result.is_err());
+        assert!(cache.entries.is_poisoned());
+
+        assert_eq!(cache.get("before"), Some(1));
+        cache.set_with_ttl("after".to_string(), 2, TTL);
+        assert_eq!(cache.get("after"), Some(2));
+        assert!(!cache.entries.is_poisoned());
+    }
 }
@@ -1,14 +1,43 @@
-use std::{ fmt::Debug, time::Duration };
+use std::{ collections::{ HashMap, HashSet, VecDeque }, fmt::Debug, sync::Arc, time::{ Duration, Instant } };
 
-use reqwest::{ Client as ReqwestClient, StatusCode };
+use reqwest::{
+    header::{ CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED },
+    Client as ReqwestClient,
+    Method,
+    RequestBuilder,
+    Response,
+    StatusCode,
+};
 use serde::{ de::DeserializeOwned, Serialize };
 use serde_json::Value;
-use tokio::sync::Mutex as AsyncMutex;
+use tokio::{ runtime::Handle, task::JoinHandle };
+
+use futures::{ stream, Stream, StreamExt, TryStreamExt };
 
 use crate::{
-    cache::Cache,
-    models::{ album::*, artist::*, page::Page, playlist::*, recommendations::*, track::* },
+    batch::BatchResult,
+    builder::{ validate_market, SpotifyClientCredentialsBuilder },
+    cache::{ Cache, CacheBackend, Cached, Conditional, Validators },
+    error::{ api_error, is_snapshot_conflict },
+    models::{
+        album::*,
+        artist::*,
+        audio::*,
+        category::*,
+        episode::*,
+        market::{ Market, MarketsResponse },
+        page::Page,
+        playlist::*,
+        recommendations::*,
+        search::*,
+        show::*,
+        track::*,
+        user::User,
+    },
+    normalize_id,
     token_manager::SpotifyTokenManager,
+    RateLimiter,
+    RetryPolicy,
     RustyError,
     RustyResult,
 };
@@ -20,7 +49,7 @@ use crate::{
 /// a unified interface for various Spotify API endpoints. The client supports server-to-server
 /// interactions with Spotify, where user authorization is not required.
 ///
-/// The `SpotifyClient` includes a token manager for handling OAuth tokens, a `reqwest` HTTP client for
+/// The `SpotifyClientCredentials` includes a token manager for handling OAuth tokens, a `reqwest` HTTP client for
 /// making requests, and a cache for storing and retrieving API responses. The cache reduces the
 /// number of requests made to the Spotify API by temporarily storing data that is likely to be reused.
 ///
@@ -36,8 +65,8 @@ use crate::{
 ///     let client_id = "your_spotify_client_id".to_string();
 ///     let client_secret = "your_spotify_client_secret".to_string();
 ///
-///     // Create a new SpotifyClient instance.
-///     let mut spotify_client = SpotifyClientCredentials::new(client_id, client_secret);
+///     // Create a new SpotifyClientCredentials instance.
+///     let spotify_client = SpotifyClientCredentials::new(client_id, client_secret);
 ///
 ///     // Example: Fetch details for a specific album.
 ///     let album_id = "4aawyAB9vmqN3uQ7FjRGTy";
@@ -60,15 +89,205 @@ pub struct SpotifyClientCredentials {
 
     /// A cache for storing responses from the Spotify API. The cache aims to reduce the number of
     /// API requests by reusing previously fetched data. The cache stores data as `serde_json::Value`,
-    /// allowing for flexible handling of different response structures.
-    cache: AsyncMutex<Cache<Value>>,
+    /// allowing for flexible handling of different response structures. In memory by default.
+    cache: Arc<dyn CacheBackend>,
+
+    /// How long responses stay cached, unless their endpoint calls for another lifetime.
+    cache_ttl: Duration,
+
+    /// The background task removing expired cache entries, if enabled; aborted on drop.
+    cache_sweeper: Option<CacheSweeper>,
+
+    /// An optional limiter consulted before every request sent to the Spotify Web API.
+    rate_limiter: Option<Arc<dyn RateLimiter>>,
+
+    /// The base URL of the Spotify Web API, overridden in tests to target a mock server.
+    api_base_url: String,
+
+    /// The market used by requests that are not given one explicitly.
+    default_market: Option<Market>,
+
+    /// The largest response body, in bytes, read from the Spotify Web API.
+    max_response_size: usize,
+
+    /// How many times a request rate limited by Spotify is retried after waiting, 0 to never retry.
+    max_rate_limit_retries: u32,
+
+    /// How requests failing with a transient server error are retried, if at all.
+    retry_policy: Option<RetryPolicy>,
 }
 
 // Define the base URL for the Spotify API as a constant
 const SPOTIFY_API_BASE_URL: &str = "https://api.spotify.com/v1";
 
+/// The default largest response body, in bytes, read from the Spotify Web API.
+pub(crate) const DEFAULT_MAX_RESPONSE_SIZE: usize = 16 * 1024 * 1024;
+
+/// The maximum number of album IDs Spotify accepts in a single request.
+const MAX_ALBUM_IDS: usize = 20;
+/// The maximum number of track IDs Spotify accepts in a single request.
+const MAX_TRACK_IDS: usize = 50;
+/// The maximum number of episode IDs Spotify accepts in a single request.
+const MAX_EPISODE_IDS: usize = 50;
+/// The maximum number of track IDs Spotify accepts in a single audio features request.
+const MAX_AUDIO_FEATURES_IDS: usize = 100;
+/// The maximum number of items Spotify returns in a single page of most paginated endpoints.
+const MAX_PAGE_LIMIT: usize = 50;
+/// The market top tracks are requested for when neither the caller nor the client picks one.
+const DEFAULT_TOP_TRACKS_MARKET: Market = Market::US;
+/// The most items collected by a call following `next` pages, guarding against runaway fetches.
+const MAX_PAGINATED_ITEMS: usize = 5000;
+/// The maximum number of requests a single batch call sends concurrently.
+const MAX_CONCURRENT_REQUESTS: usize = 4;
+/// The largest `offset + limit` Spotify accepts on search and browse endpoints.
+const MAX_OFFSET_PLUS_LIMIT: usize = 1000;
+
+/// How long responses stay cached, unless their endpoint calls for another lifetime.
+pub(crate) const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(600);
+/// How long rarely changing responses, such as genre seeds, markets and browse categories, stay cached.
+const LONG_CACHE_TTL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Builds the cache key of a single track, as fetched for `market`.
+fn track_cache_key(track_id: &str, market: Option<&str>) -> String {
+    match market {
+        Some(market) => format!("/tracks/{track_id}?market={market}"),
+        None => format!("/tracks/{track_id}"),
+    }
+}
+
+/// A background task periodically removing the expired entries of a client's cache.
+///
+/// The task is aborted when the sweeper is dropped along with its client.
+struct CacheSweeper {
+    /// The handle of the spawned task.
+    task: JoinHandle<()>,
+}
+
+impl CacheSweeper {
+    /// Spawns a task sweeping `cache` every `interval` on the current Tokio runtime.
+    fn spawn(cache: Arc<dyn CacheBackend>, interval: Duration) -> RustyResult<Self> {
+        let runtime = Handle::try_current().map_err(|e| {
+            RustyError::Unexpected(format!("sweeping the cache requires a Tokio runtime: {e}"))
+        })?;
+        let task = runtime.spawn(async move {
+            let mut ticks = tokio::time::interval(interval);
+            // The first tick completes immediately, while the cache is still empty
+            ticks.tick().await;
+            loop {
+                ticks.tick().await;
+                let removed = cache.remove_expired();
+                log::debug!("removed {removed} expired cache entries");
+            }
+        });
+        Ok(CacheSweeper { task })
+    }
+
+    /// Aborts the task and waits until it has stopped.
+    async fn stop(mut self) {
+        self.task.abort();
+        // The task can only end by being aborted, so the cancellation error is expected
+        let _ = (&mut self.task).await;
+    }
+}
+
+impl Drop for CacheSweeper {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Rejects a page past the 1000-result cap of search and browse endpoints, which Spotify would
+/// answer with a `400 Bad Request`.
+fn validate_offset(offset: usize, limit: usize) -> RustyResult<()> {
+    if offset + limit > MAX_OFFSET_PLUS_LIMIT {
+        return Err(
+            RustyError::invalid_input(
+                &format!(
+                    "offset + limit must not exceed {MAX_OFFSET_PLUS_LIMIT}, got offset {offset} + limit {limit}"
+                )
+            )
+        );
+    }
+    Ok(())
+}
+
+/// Reads the environment variable `name`, failing with an error naming it.
+pub(crate) fn env_var(name: &str) -> RustyResult<String> {
+    std::env::var(name).map_err(|e| {
+        RustyError::invalid_input(&format!("failed to read environment variable `{name}`: {e}"))
+    })
+}
+
+/// Percent-encodes a query parameter value, so that characters such as `&` or spaces cannot
+/// break the query string.
+fn encode_query_value(value: &str) -> String {
+    url::form_urlencoded::byte_serialize(value.as_bytes()).collect()
+}
+
+/// Checks whether a `Content-Type` header value denotes JSON, e.g. `application/json; charset=utf-8`.
+fn is_json_content_type(content_type: &str) -> bool {
+    let media_type = content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+    media_type == "application/json" || media_type.ends_with("+json")
+}
+
+/// Reads the number of seconds to wait from the `Retry-After` header of a rate limited `response`.
+fn retry_after(response: &Response) -> Option<u64> {
+    response
+        .headers()
+        .get("Retry-After")
+        .and_then(|h| h.to_str().ok())
+        .and_then(|s| s.parse::<u64>().ok())
+}
+
+/// Reads the body of `response`, failing as soon as it grows beyond `max_size` bytes.
+///
+/// The body is streamed rather than buffered all at once, so an oversized response is dropped
+/// before it is held in memory.
+async fn read_body(mut response: Response, max_size: usize) -> RustyResult<Vec<u8>> {
+    if response.content_length().is_some_and(|length| length > (max_size as u64)) {
+        return Err(RustyError::ResponseTooLarge(max_size));
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if body.len() + chunk.len() > max_size {
+            return Err(RustyError::ResponseTooLarge(max_size));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
+
+/// Picks the cache TTL for the response of `path`, or `None` to use the cache's default TTL.
+fn cache_ttl_for(path: &str) -> Option<Duration> {
+    let is_categories = path.starts_with("/browse/categories") && !path.contains("/playlists");
+    if
+        path.starts_with("/recommendations/available-genre-seeds") ||
+        path.starts_with("/markets") ||
+        is_categories
+    {
+        Some(LONG_CACHE_TTL)
+    } else {
+        None
+    }
+}
+
+/// The state of a [`SpotifyClientCredentials::paginate`] stream.
+struct Paginator<'a, T> {
+    /// The client fetching the pages.
+    client: &'a SpotifyClientCredentials,
+    /// The items of the current page that were not yielded yet.
+    items: VecDeque<T>,
+    /// The URL of the next page to fetch, if any.
+    next_url: Option<String>,
+    /// The paths of the pages fetched so far.
+    visited: HashSet<String>,
+    /// The offset of the last page fetched.
+    last_offset: Option<u32>,
+}
+
 impl SpotifyClientCredentials {
-    /// Creates a new instance of `SpotifyClient`.
+    /// Creates a new instance of `SpotifyClientCredentials`.
     ///
     /// Initializes the client with client ID and secret for authentication.
     ///
@@ -77,13 +296,207 @@ impl SpotifyClientCredentials {
     /// * `client_id` - The Spotify client ID.
     /// * `client_secret` - The Spotify client secret.
     pub fn new(client_id: String, client_secret: String) -> Self {
-        let token_manager: SpotifyTokenManager = SpotifyTokenManager::new(client_id, client_secret);
-        let http_client: ReqwestClient = ReqwestClient::new();
+        Self::with_cache_ttl(client_id, client_secret, DEFAULT_CACHE_TTL)
+    }
+
+    /// Creates a new instance of `SpotifyClientCredentials` whose responses stay cached for `ttl`.
+    ///
+    /// Shorten the TTL when the data requested changes often, or lengthen it for static data.
+    /// Endpoints with their own lifetime, such as genre seeds and markets, keep it.
+    ///
+    /// # Arguments
+    ///
+    /// * `client_id` - The Spotify client ID.
+    /// * `client_secret` - The Spotify client secret.
+    /// * `ttl` - How long responses stay cached. `new` uses 10 minutes.
+    pub fn with_cache_ttl(client_id: String, client_secret: String, ttl: Duration) -> Self {
+        let mut client = Self::with_http_client(client_id, client_secret, ReqwestClient::new());
+        client.cache_ttl = ttl;
+        client
+    }
+
+    /// Returns a [`SpotifyClientCredentialsBuilder`] for configuring the client before creating it,
+    /// e.g. to route requests through a proxy.
+    ///
+    /// # Arguments
+    ///
+    /// * `client_id` - The Spotify client ID.
+    /// * `client_secret` - The Spotify client secret.
+    pub fn builder(client_id: String, client_secret: String) -> SpotifyClientCredentialsBuilder {
+        SpotifyClientCredentialsBuilder::new(client_id, client_secret)
+    }
+
+    /// Creates a client from the `SPOTIFY_CLIENT_ID` and `SPOTIFY_CLIENT_SECRET` environment
+    /// variables.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RustyError::Io` with `InvalidInput`, naming the variable, if either variable is
+    /// missing or not valid unicode.
+    pub fn from_env() -> RustyResult<Self> {
+        Self::from_env_with_names("SPOTIFY_CLIENT_ID", "SPOTIFY_CLIENT_SECRET")
+    }
+
+    /// Creates a client from the client ID and secret stored in the environment variables
+    /// `id_var` and `secret_var`, e.g. for applications namespacing their variables.
+    ///
+    /// # Arguments
+    ///
+    /// * `id_var` - The name of the variable holding the client ID, e.g. `MYAPP_SPOTIFY_CLIENT_ID`.
+    /// * `secret_var` - The name of the variable holding the client secret.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RustyError::Io` with `InvalidInput`, naming the variable, if either variable is
+    /// missing or not valid unicode.
+    pub fn from_env_with_names(id_var: &str, secret_var: &str) -> RustyResult<Self> {
+        Ok(Self::new(env_var(id_var)?, env_var(secret_var)?))
+    }
+
+    /// Creates a new instance of `SpotifyClientCredentials` sending its API requests and its token
+    /// requests through `http_client`.
+    ///
+    /// This is the hook for configuring the HTTP layer: set request timeouts, proxy settings, a TLS
+    /// backend or connection pool limits on the `reqwest::Client`, and both the client and its
+    /// token manager use them. Cloning a `reqwest::Client` shares its connection pool, so the same
+    /// client can also be passed to several `SpotifyClientCredentials`.
+    ///
+    /// # Arguments
+    ///
+    /// * `client_id` - The Spotify client ID.
+    /// * `client_secret` - The Spotify client secret.
+    /// * `http_client` - The `reqwest::Client` to send every request through.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use rustyspoty::SpotifyClientCredentials;
+    ///
+    /// let http_client = reqwest::Client::builder()
+    ///     .timeout(Duration::from_secs(10))
+    ///     .build()
+    ///     .expect("valid HTTP client configuration");
+    /// let client = SpotifyClientCredentials::with_http_client(
+    ///     "client_id".to_string(),
+    ///     "client_secret".to_string(),
+    ///     http_client
+    /// );
+    /// ```
+    pub fn with_http_client(
+        client_id: String,
+        client_secret: String,
+        http_client: ReqwestClient
+    ) -> Self {
+        let token_manager: SpotifyTokenManager = SpotifyTokenManager::with_http_client(
+            client_id,
+            client_secret,
+            http_client.clone()
+        );
+        Self::from_token_manager(token_manager, http_client)
+    }
+
+    /// Creates a client authorized by `token_manager`, sending its API requests through `http_client`.
+    pub(crate) fn from_token_manager(
+        token_manager: SpotifyTokenManager,
+        http_client: ReqwestClient
+    ) -> Self {
         SpotifyClientCredentials {
             token_manager,
             http_client,
-            cache: AsyncMutex::new(Cache::new(Duration::from_secs(600))),
+            cache: Arc::new(Cache::new()),
+            cache_ttl: DEFAULT_CACHE_TTL,
+            cache_sweeper: None,
+            rate_limiter: None,
+            api_base_url: SPOTIFY_API_BASE_URL.to_string(),
+            default_market: None,
+            max_response_size: DEFAULT_MAX_RESPONSE_SIZE,
+            max_rate_limit_retries: 0,
+            retry_policy: None,
+        }
+    }
+
+    /// Creates a client from the settings collected by a [`SpotifyClientCredentialsBuilder`].
+    pub(crate) fn from_builder(builder: SpotifyClientCredentialsBuilder) -> RustyResult<Self> {
+        let http_client = builder.http_client_builder
+            .build()
+            .map_err(|e| RustyError::Unexpected(format!("failed to build HTTP client: {e}")))?;
+        let mut client = Self::with_http_client(
+            builder.client_id,
+            builder.client_secret,
+            http_client
+        );
+        client.rate_limiter = builder.rate_limiter;
+        client.default_market = builder.default_market;
+        client.max_response_size = builder.max_response_size;
+        client.max_rate_limit_retries = builder.max_rate_limit_retries;
+        client.retry_policy = builder.retry_policy;
+        client.cache_ttl = builder.cache_ttl;
+        client.cache = match builder.cache_backend {
+            Some(cache_backend) => cache_backend,
+            None => {
+                let mut cache = Cache::new();
+                if let Some(max_entries) = builder.cache_max_entries {
+                    cache = cache.with_max_entries(max_entries);
+                }
+                Arc::new(cache)
+            }
+        };
+        if let Some(interval) = builder.cache_sweep_interval {
+            client.cache_sweeper = Some(CacheSweeper::spawn(Arc::clone(&client.cache), interval)?);
+        }
+        Ok(client)
+    }
+
+    /// Sends the API and token requests to a mock server at `server_uri` instead of Spotify.
+    #[cfg(test)]
+    pub(crate) fn use_mock_server(&mut self, server_uri: &str) {
+        self.api_base_url = format!("{server_uri}/v1");
+        self.token_manager.set_token_url(format!("{server_uri}/api/token"));
+    }
+
+    /// Picks the market of a request: `market` if given, the client's default market otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RustyError::Io` with `InvalidInput` for `from_token` unless the client is
+    /// authorized on behalf of a user.
+    pub(crate) fn resolve_market(&self, market: Option<Market>) -> RustyResult<Option<String>> {
+        let market = market.or(self.default_market);
+        match market {
+            Some(market) if !self.token_manager.is_user() => {
+                validate_market(market).map(|market| Some(market.to_string()))
+            }
+            _ => Ok(market.map(String::from)),
+        }
+    }
+
+    /// Shuts the client down, stopping its background tasks such as the cache sweeper, and
+    /// flushing its cache backend.
+    ///
+    /// Dropping the client is enough to stop its background tasks, as they are aborted on drop.
+    /// Unlike dropping, `shutdown` also waits until the tasks have stopped, so they no longer
+    /// hold on to the cache once it returns. Cleanup that has to run asynchronously, like
+    /// [`CacheBackend::flush`], only happens here.
+    ///
+    /// # Example
+    /// ```
+    /// # use std::time::Duration;
+    /// # use rustyspoty::SpotifyClientCredentials;
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = SpotifyClientCredentials::builder("client_id".to_string(), "client_secret".to_string())
+    ///     .with_cache_sweep_interval(Duration::from_secs(60))
+    ///     .build()?;
+    /// // ...
+    /// client.shutdown().await;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn shutdown(mut self) {
+        if let Some(cache_sweeper) = self.cache_sweeper.take() {
+            cache_sweeper.stop().await;
         }
+        self.cache.flush().await;
     }
 
     /// Updates the cache with a new value for a given key or inserts it if the key does not exist.
@@ -98,12 +511,12 @@ impl SpotifyClientCredentials {
     /// ```
     /// # use rustyspoty::SpotifyClientCredentials;
     /// # async fn example() {
-    /// # let mut client_credentials = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// # let client_credentials = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
     /// client_credentials.update_cache("artist:1".to_string(), serde_json::json!({"name": "Artist Name"})).await;
     /// # }
     /// ```
     pub async fn update_cache(&self, key: String, value: Value) {
-        self.cache.lock().await.set(key, value);
+        self.cache.set(key, value, self.cache_ttl);
     }
 
     /// Retrieves a value from the cache if it exists and has not expired.
@@ -121,13 +534,121 @@ impl SpotifyClientCredentials {
     /// ```
     /// # use rustyspoty::SpotifyClientCredentials;
     /// # async fn example() -> Option<serde_json::Value> {
-    /// # let mut client_credentials = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// # let client_credentials = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
     /// let value = client_credentials.check_cache("artist:1").await;
     /// value
     /// # }
     /// ```
     pub async fn check_cache(&self, key: &str) -> Option<Value> {
-        self.cache.lock().await.get(key)
+        self.cache.get(key)
+    }
+
+    /// Removes the cached response stored under `key`, so that the next request for it is sent
+    /// to Spotify.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The cache key, i.e. the request path such as `/albums/{id}`, including its query
+    ///   string if it had one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rustyspoty::SpotifyClientCredentials;
+    /// # async fn example() {
+    /// # let client_credentials = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// client_credentials.invalidate("/albums/4aawyAB9vmqN3uQ7FjRGTy").await;
+    /// # }
+    /// ```
+    pub async fn invalidate(&self, key: &str) {
+        self.cache.remove(key);
+    }
+
+    /// Removes every cached response.
+    pub async fn clear_cache(&self) {
+        self.cache.clear();
+    }
+
+    /// Removes every cached response of the playlist `playlist_id`, such as the playlist itself
+    /// and its pages of tracks.
+    ///
+    /// Playlists edited through [`SpotifyUserClient`](crate::SpotifyUserClient) are invalidated
+    /// automatically. Call this after editing a playlist by other means, so that the next
+    /// `get_playlist` refetches it instead of returning the stale cached version.
+    ///
+    /// # Returns
+    ///
+    /// The number of cache entries removed.
+    pub async fn invalidate_playlist(&self, playlist_id: &str) -> usize {
+        let prefix = format!("/playlists/{playlist_id}");
+        self.cache.remove_matching(
+            &(|key: &str| {
+                key.strip_prefix(&prefix).is_some_and(|rest| {
+                    rest.is_empty() || rest.starts_with('/') || rest.starts_with('?')
+                })
+            })
+        )
+    }
+
+    /// Returns the number of entries in the cache, including expired entries that have not been
+    /// removed yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rustyspoty::SpotifyClientCredentials;
+    /// # async fn example() {
+    /// # let client_credentials = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// println!("{} cached responses", client_credentials.cache_len().await);
+    /// # }
+    /// ```
+    pub async fn cache_len(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Removes the expired entries from the cache.
+    ///
+    /// Expired entries are never served, but stay in memory until they are overwritten. Call
+    /// this periodically to keep the memory of a long-lived client in check, or let the client
+    /// do it with [`with_cache_sweep_interval`](SpotifyClientCredentialsBuilder::with_cache_sweep_interval).
+    ///
+    /// # Returns
+    ///
+    /// The number of entries removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rustyspoty::SpotifyClientCredentials;
+    /// # async fn example() {
+    /// # let client_credentials = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// let removed = client_credentials.cleanup_cache().await;
+    /// println!("removed {removed} expired responses, {} left", client_credentials.cache_len().await);
+    /// # }
+    /// ```
+    pub async fn cleanup_cache(&self) -> usize {
+        self.cache.remove_expired()
+    }
+
+    /// Checks whether the cache holds an unexpired value for `key`, without counting as an access.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The cache key, i.e. the request path such as `/albums/{id}`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rustyspoty::SpotifyClientCredentials;
+    /// # async fn example() {
+    /// # let client_credentials = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// if client_credentials.cache_contains("/albums/4aawyAB9vmqN3uQ7FjRGTy").await {
+    ///     println!("The album is cached");
+    /// }
+    /// # }
+    /// ```
+    pub async fn cache_contains(&self, key: &str) -> bool {
+        self.cache.contains_key(key)
     }
 
     /// Performs a GET request to the specified Spotify API endpoint.
@@ -142,51 +663,243 @@ impl SpotifyClientCredentials {
     /// # Returns
     ///
     /// A `Result` containing either the deserialized response data or an error.
-    async fn get_spotify_data<T>(&mut self, path: &str) -> RustyResult<T>
+    async fn get_spotify_data<T>(&self, path: &str) -> RustyResult<T>
+        where
+            T: DeserializeOwned + Serialize + Debug // Ensure T can be serialized for caching
+    {
+        Ok(self.get_spotify_data_cached(path).await?.value)
+    }
+
+    /// Performs a GET request to the specified Spotify API endpoint, reporting whether the
+    /// response was served from the cache.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The specific endpoint path after the base URL.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either the deserialized response data wrapped in [`Cached`] or an error.
+    async fn get_spotify_data_cached<T>(&self, path: &str) -> RustyResult<Cached<T>>
         where
             T: DeserializeOwned + Serialize + Debug // Ensure T can be serialized for caching
     {
         let cache_key = path.to_string();
 
         // Attempt to retrieve from cache first
-        {
-            // Scope for the cache lock to ensure it's dropped before await points
-            let cache_lock = self.cache.lock().await;
-            if let Some(cached) = cache_lock.get(&cache_key) {
-                // Deserialize the cached JSON to the requested type
-                if let Ok(cached_data) = serde_json::from_value::<T>(cached.clone()) {
-                    return Ok(cached_data);
-                }
+        if let Some((cached, stored_at)) = self.cache.get_with_stored_at(&cache_key) {
+            // Deserialize the cached JSON to the requested type
+            if let Ok(cached_data) = serde_json::from_value::<T>(cached) {
+                return Ok(Cached { value: cached_data, from_cache: true, fetched_at: stored_at });
             }
-        } // Cache lock is dropped here
+        }
 
         // Proceed with API request if not found in cache or cache is stale
+        let data = self.send_spotify_request::<T, ()>(Method::GET, path, None).await?;
+        let ttl = cache_ttl_for(&cache_key).unwrap_or(self.cache_ttl);
+        self.cache.set(cache_key, serde_json::to_value(&data)?, ttl);
+        Ok(Cached { value: data, from_cache: false, fetched_at: Instant::now() })
+    }
+
+    /// Fetches data from the Spotify Web API unless it did not change since `validators` were
+    /// obtained, bypassing the client's cache.
+    ///
+    /// Sends the ETag in `If-None-Match` and the date in `If-Modified-Since`. Per HTTP semantics,
+    /// a server supporting both only considers `If-None-Match`.
+    async fn get_spotify_data_if_modified<T>(
+        &self,
+        path: &str,
+        validators: &Validators
+    ) -> RustyResult<Conditional<T>>
+        where T: DeserializeOwned
+    {
+        let token = self.token_manager.get_valid_token().await?;
+        let mut request = self.authorized_request(&token, Method::GET, path);
+        if let Some(etag) = &validators.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &validators.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+        let response = self.execute(request).await?;
+        if response.status() == StatusCode::NOT_MODIFIED {
+            return Ok(Conditional::NotModified);
+        }
+
+        let headers = response.headers();
+        let validators = Validators {
+            etag: headers
+                .get(ETAG)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string),
+            last_modified: headers
+                .get(LAST_MODIFIED)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string),
+        };
+        let value = self.parse_response(response).await?;
+        Ok(Conditional::Modified(value, validators))
+    }
+
+    /// Sends a request with any HTTP method to the specified Spotify API endpoint.
+    ///
+    /// This method handles authorization and rate limiting, serializes the optional `body` as JSON,
+    /// and deserializes the response into the specified type. Responses are never cached; GET
+    /// requests that should be cached go through `get_spotify_data` instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The HTTP method of the request.
+    /// * `path` - The specific endpoint path after the base URL.
+    /// * `body` - An optional body, sent as JSON.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either the deserialized response data or an error. Empty responses,
+    /// such as `204 No Content`, are deserialized from `null`, so they can be read as `()` or `None`.
+    pub(crate) async fn send_spotify_request<T, B>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&B>
+    ) -> RustyResult<T>
+        where T: DeserializeOwned, B: Serialize + ?Sized
+    {
         let token = self.token_manager.get_valid_token().await?;
-        let url = format!("{SPOTIFY_API_BASE_URL}{path}");
-        let response = self.http_client
-            .get(&url)
-            .header("Authorization", format!("Bearer {token}"))
-            .send().await?;
+        self.send_spotify_request_with_token(&token, method, path, body).await
+    }
+
+    /// Sends a POST request with a JSON `body` to the specified Spotify API endpoint, like
+    /// `send_spotify_request`.
+    pub(crate) async fn post_spotify_data<T, B>(&self, path: &str, body: &B) -> RustyResult<T>
+        where T: DeserializeOwned, B: Serialize + ?Sized
+    {
+        self.send_spotify_request(Method::POST, path, Some(body)).await
+    }
+
+    /// Sends a PUT request with a JSON `body` to the specified Spotify API endpoint, like
+    /// `send_spotify_request`.
+    pub(crate) async fn put_spotify_data<T, B>(&self, path: &str, body: &B) -> RustyResult<T>
+        where T: DeserializeOwned, B: Serialize + ?Sized
+    {
+        self.send_spotify_request(Method::PUT, path, Some(body)).await
+    }
+
+    /// Sends a DELETE request with a JSON `body` to the specified Spotify API endpoint, like
+    /// `send_spotify_request`.
+    pub(crate) async fn delete_spotify_data<T, B>(&self, path: &str, body: &B) -> RustyResult<T>
+        where T: DeserializeOwned, B: Serialize + ?Sized
+    {
+        self.send_spotify_request(Method::DELETE, path, Some(body)).await
+    }
+
+    /// Sends a request like `send_spotify_request`, authorized with an already acquired `token`.
+    ///
+    /// Only borrows the client immutably, so several requests can be in flight at once.
+    async fn send_spotify_request_with_token<T, B>(
+        &self,
+        token: &str,
+        method: Method,
+        path: &str,
+        body: Option<&B>
+    ) -> RustyResult<T>
+        where T: DeserializeOwned, B: Serialize + ?Sized
+    {
+        let mut request = self.authorized_request(token, method, path);
+        if let Some(body) = body {
+            request = request.json(body);
+        }
+        let response = self.execute(request).await?;
+        self.parse_response(response).await
+    }
+
+    /// Builds a request to the Spotify Web API at `path`, authorized with `token`.
+    fn authorized_request(&self, token: &str, method: Method, path: &str) -> RequestBuilder {
+        let url = format!("{}{path}", self.api_base_url);
+        self.http_client.request(method, &url).header("Authorization", format!("Bearer {token}"))
+    }
+
+    /// Sends `request` once the rate limiter, if any, allows it.
+    ///
+    /// A `429 Too Many Requests` response is retried after waiting for its `Retry-After`, up to
+    /// `max_rate_limit_retries` times, and a transient server error as the retry policy, if any,
+    /// allows. The last response is returned as is once the retries run out.
+    async fn execute(&self, mut request: RequestBuilder) -> RustyResult<Response> {
+        let mut rate_limit_retries = 0;
+        let mut server_error_retries = 0;
+        loop {
+            if let Some(rate_limiter) = &self.rate_limiter {
+                rate_limiter.acquire().await;
+            }
+            // Requests whose body cannot be cloned are sent only once
+            let retry = request.try_clone();
+            let response = request.send().await?;
+            let status = response.status();
+            let delay = if status == StatusCode::TOO_MANY_REQUESTS {
+                match retry_after(&response) {
+                    Some(retry_after) if rate_limit_retries < self.max_rate_limit_retries => {
+                        rate_limit_retries += 1;
+                        log::warn!(
+                            "rate limited by the Spotify Web API, retrying in {retry_after} seconds"
+                        );
+                        Duration::from_secs(retry_after)
+                    }
+                    _ => {
+                        return Ok(response);
+                    }
+                }
+            } else if RetryPolicy::is_retryable(status) {
+                match self.retry_policy {
+                    Some(policy) if server_error_retries < policy.max_retries => {
+                        let delay = policy.delay(server_error_retries);
+                        server_error_retries += 1;
+                        log::warn!(
+                            "Spotify Web API responded with {status}, retrying in {delay:?}"
+                        );
+                        delay
+                    }
+                    _ => {
+                        return Ok(response);
+                    }
+                }
+            } else {
+                return Ok(response);
+            };
+            let Some(retry) = retry else {
+                return Ok(response);
+            };
+            tokio::time::sleep(delay).await;
+            request = retry;
+        }
+    }
 
+    /// Deserializes a successful `response` into `T`, or turns a failed one into a `RustyError`.
+    async fn parse_response<T>(&self, response: Response) -> RustyResult<T> where T: DeserializeOwned {
         // Handle rate limiting or other errors as needed here
         match response.status() {
-            StatusCode::OK => {
-                let data = response.json::<T>().await?;
-                {
-                    // Scope for the cache lock to ensure it's dropped right after use
-                    let cache_lock = self.cache.lock().await;
-                    cache_lock.set(cache_key, serde_json::to_value(&data)?);
-                } // Cache lock is dropped here
-                Ok(data)
+            status if status.is_success() => {
+                let content_type = response
+                    .headers()
+                    .get(CONTENT_TYPE)
+                    .map(|value| String::from_utf8_lossy(value.as_bytes()).into_owned());
+                let bytes = read_body(response, self.max_response_size).await?;
+                if bytes.is_empty() {
+                    // Nothing to parse, e.g. `204 No Content` from write endpoints
+                    return Ok(serde_json::from_value(Value::Null)?);
+                }
+                if let Some(content_type) = content_type.filter(|c| !is_json_content_type(c)) {
+                    // Typically a login page served by a captive portal or an intercepting proxy
+                    let body_start: String = String::from_utf8_lossy(&bytes).chars().take(64).collect();
+                    return Err(
+                        RustyError::Unexpected(
+                            format!("expected JSON, got {content_type}; body starts with {body_start}")
+                        )
+                    );
+                }
+                Ok(serde_json::from_slice(&bytes)?)
             }
             StatusCode::TOO_MANY_REQUESTS => {
-                if
-                    let Some(retry_after) = response
-                        .headers()
-                        .get("Retry-After")
-                        .and_then(|h| h.to_str().ok())
-                        .and_then(|s| s.parse::<u64>().ok())
-                {
+                if let Some(retry_after) = retry_after(&response) {
                     // Convert retry_after to a Duration
                     // let wait_time = Duration::from_secs(retry_after);
                     // Retry the request or return an error indicating rate limiting
@@ -201,44 +914,275 @@ impl SpotifyClientCredentials {
                     )
                 }
             }
-            _ => {
+            status => {
                 // Handle other errors based on status code
-                Err(
-                    RustyError::Unexpected(
-                        format!("API request failed with status: {}", response.status())
-                    )
-                )
+                let body = response.text().await.unwrap_or_default();
+                if is_snapshot_conflict(status, &body) {
+                    return Err(RustyError::SnapshotConflict(body));
+                }
+                Err(api_error(status, &body))
+            }
+        }
+    }
+
+    /// Fetches the page at `path` and follows its `next` links, collecting the items of every page.
+    ///
+    /// Stops early, with a warning, once `max_items` items were collected, or when a `next` link
+    /// does not move past the current page, which would otherwise loop forever.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the first page, including its query string.
+    /// * `max_items` - The most items to collect.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first page that fails, or `RustyError::Unexpected` if a `next`
+    /// link points outside of the Spotify Web API.
+    async fn fetch_all<T>(&self, path: &str, max_items: usize) -> RustyResult<Vec<T>>
+        where T: DeserializeOwned + Serialize + Debug
+    {
+        let mut items = Vec::new();
+        let mut next_path = Some(path.to_string());
+        let mut visited = HashSet::new();
+        let mut last_offset = None;
+
+        while let Some(path) = next_path.take() {
+            if !visited.insert(path.clone()) {
+                log::warn!("stopped following pages at {path}, which was already fetched");
+                break;
+            }
+            let page: Page<T> = self.get_spotify_data(&path).await?;
+            if last_offset.is_some_and(|last_offset| page.offset <= last_offset) {
+                log::warn!("stopped following pages at {path}, which repeats offset {}", page.offset);
+                break;
+            }
+            last_offset = Some(page.offset);
+            items.extend(page.items);
+            if items.len() >= max_items {
+                if page.next.is_some() {
+                    log::warn!("stopped following pages of {path} after {max_items} items");
+                }
+                items.truncate(max_items);
+                break;
+            }
+            next_path = page.next.map(|next| self.relative_path(&next)).transpose()?;
+        }
+
+        Ok(items)
+    }
+
+    /// Streams the items of the page at `first_path` and of the pages its `next` links lead to.
+    ///
+    /// Pages are fetched lazily, one at a time as the stream is polled, and each page is cached
+    /// like any other response. Stop polling the stream, or drop it, to stop fetching pages.
+    ///
+    /// The stream borrows the client until it is dropped. Other requests can still be sent through
+    /// the client while paginating.
+    ///
+    /// # Arguments
+    ///
+    /// * `first_path` - The path of the first page relative to the API base URL, including its
+    ///   query string, e.g. `"/albums/4aawyAB9vmqN3uQ7FjRGTy/tracks?limit=50"`.
+    ///
+    /// # Returns
+    ///
+    /// A stream of the items in page order. An error, whether fetching a page fails or a `next`
+    /// link points outside of the Spotify Web API, is yielded as the last item. The stream also
+    /// ends, with a warning, when a `next` link does not move past the current page.
+    ///
+    /// # Example
+    /// ```
+    /// # use rustyspoty::{ models::track::SimplifiedTrack, SpotifyClientCredentials };
+    /// use futures::StreamExt;
+    ///
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// let tracks = client.paginate::<SimplifiedTrack>("/albums/4aawyAB9vmqN3uQ7FjRGTy/tracks?limit=50");
+    /// let mut tracks = std::pin::pin!(tracks);
+    /// while let Some(track) = tracks.next().await {
+    ///     println!("Track: {}", track?.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn paginate<'a, T>(&'a self, first_path: &str) -> impl Stream<Item = RustyResult<T>> + 'a
+        where T: DeserializeOwned + Serialize + Debug + 'a
+    {
+        let state = Paginator {
+            next_url: Some(format!("{}{first_path}", self.api_base_url)),
+            client: self,
+            items: VecDeque::new(),
+            visited: HashSet::new(),
+            last_offset: None,
+        };
+
+        stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(item) = state.items.pop_front() {
+                    return Some((Ok(item), state));
+                }
+
+                let url = state.next_url.take()?;
+                let path = match state.client.relative_path(&url) {
+                    Ok(path) => path,
+                    Err(e) => {
+                        return Some((Err(e), state));
+                    }
+                };
+                if !state.visited.insert(path.clone()) {
+                    log::warn!("stopped following pages at {path}, which was already fetched");
+                    return None;
+                }
+
+                let page: Page<T> = match state.client.get_spotify_data(&path).await {
+                    Ok(page) => page,
+                    Err(e) => {
+                        return Some((Err(e), state));
+                    }
+                };
+                if state.last_offset.is_some_and(|last_offset| page.offset <= last_offset) {
+                    log::warn!("stopped following pages at {path}, which repeats offset {}", page.offset);
+                    return None;
+                }
+                state.last_offset = Some(page.offset);
+                state.items.extend(page.items);
+                state.next_url = page.next;
             }
+        })
+    }
+
+    /// Turns a URL returned by Spotify, such as a page's `next` link, into a path relative to
+    /// the API base URL.
+    fn relative_path(&self, url: &str) -> RustyResult<String> {
+        url.strip_prefix(self.api_base_url.as_str())
+            .or_else(|| url.strip_prefix(SPOTIFY_API_BASE_URL))
+            .map(str::to_string)
+            .ok_or_else(|| RustyError::Unexpected(format!("unexpected page URL: {url}")))
+    }
+
+    /// Fetches `ids` in chunks of at most `chunk_size` IDs, the per-request maximum of the endpoint.
+    ///
+    /// Up to `MAX_CONCURRENT_REQUESTS` chunks are requested at once. The responses are returned in
+    /// the order of the chunks, and are not cached.
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - The IDs to fetch.
+    /// * `chunk_size` - The maximum number of IDs per request.
+    /// * `path_for_ids` - Builds the request path from a comma-separated list of IDs.
+    async fn fetch_in_chunks<T>(
+        &self,
+        ids: &[String],
+        chunk_size: usize,
+        path_for_ids: impl Fn(&str) -> String
+    ) -> RustyResult<Vec<T>>
+        where T: DeserializeOwned
+    {
+        if ids.is_empty() {
+            return Ok(Vec::new());
         }
+
+        let token = self.token_manager.get_valid_token().await?;
+        let client = self;
+        let paths: Vec<String> = ids
+            .chunks(chunk_size)
+            .map(|chunk| path_for_ids(&chunk.join(",")))
+            .collect();
+        stream
+            ::iter(paths)
+            .map(|path| {
+                let token = &token;
+                async move {
+                    client.send_spotify_request_with_token::<T, ()>(token, Method::GET, &path, None).await
+                }
+            })
+            .buffered(MAX_CONCURRENT_REQUESTS)
+            .try_collect().await
     }
 
     /// Fetches detailed information about a specific album by its Spotify ID.
     ///
     /// # Arguments
-    /// * `album_id` - The Spotify ID of the album.
+    /// * `album_id` - The Spotify ID, URI or `open.spotify.com` URL of the album.
     ///
     /// # Returns
     /// * `Result<Album, RustyError>`: On success, returns an `Album` object containing detailed information about the album. On failure, returns a `RustyError` detailing the issue.
     ///
     /// # Errors
     /// * Returns an error for invalid album ID, network issues, or problems with the Spotify API.
+    /// * Returns `RustyError::Io` with `InvalidInput` if `album_id` is a URI or URL of another
+    ///   resource type, e.g. a track.
     ///
     /// # Example
     /// ```
     /// # use rustyspoty::SpotifyClientCredentials;
     /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
-    /// # let mut spotify_client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// # let spotify_client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
     /// let album_id = "1DFixLWuPkv3KT3TnV35m3";
     /// let album = spotify_client.get_album(album_id).await?;
     /// println!("Album name: {}", album.name);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn get_album(&mut self, album_id: &str) -> RustyResult<Album> {
-        let path = format!("/albums/{album_id}");
+    pub async fn get_album(&self, album_id: &str) -> RustyResult<Album> {
+        let path = format!("/albums/{}", normalize_id(album_id, "album")?);
         self.get_spotify_data(&path).await
     }
 
+    /// Fetches an album like [`get_album`](Self::get_album), also reporting whether it was served
+    /// from the cache and when it was fetched from Spotify.
+    ///
+    /// # Arguments
+    /// * `album_id` - The Spotify ID, URI or `open.spotify.com` URL of the album.
+    ///
+    /// # Example
+    /// ```
+    /// # use rustyspoty::SpotifyClientCredentials;
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let spotify_client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// let album = spotify_client.get_album_cached("1DFixLWuPkv3KT3TnV35m3").await?;
+    /// println!("{} (from cache: {}, age: {:?})", album.value.name, album.from_cache, album.fetched_at.elapsed());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_album_cached(&self, album_id: &str) -> RustyResult<Cached<Album>> {
+        let path = format!("/albums/{}", normalize_id(album_id, "album")?);
+        self.get_spotify_data_cached(&path).await
+    }
+
+    /// Fetches an album unless it did not change since `validators` were obtained.
+    ///
+    /// Meant for applications that keep albums outside of this crate, e.g. in a database, along
+    /// with the validators of the response they came from. The request bypasses the client's
+    /// cache, and a `NotModified` answer costs no download.
+    ///
+    /// # Arguments
+    /// * `album_id` - The Spotify ID, URI or `open.spotify.com` URL of the album.
+    /// * `validators` - The validators returned with the stored album. Spotify may ignore
+    ///   `last_modified`, in which case the album is downloaded again.
+    ///
+    /// # Example
+    /// ```
+    /// # use rustyspoty::{ Conditional, SpotifyClientCredentials, Validators };
+    /// # async fn run(validators: Validators) -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// match client.get_album_if_modified("4aawyAB9vmqN3uQ7FjRGTy", &validators).await? {
+    ///     Conditional::NotModified => println!("The stored album is up to date"),
+    ///     Conditional::Modified(album, validators) => println!("{} changed, new ETag {:?}", album.name, validators.etag),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_album_if_modified(
+        &self,
+        album_id: &str,
+        validators: &Validators
+    ) -> RustyResult<Conditional<Album>> {
+        let path = format!("/albums/{}", normalize_id(album_id, "album")?);
+        self.get_spotify_data_if_modified(&path, validators).await
+    }
+
     /// Fetches detailed information for several albums based on their Spotify IDs.
     ///
     /// This method first checks if the requested album information is available in the cache
@@ -252,25 +1196,27 @@ impl SpotifyClientCredentials {
     ///
     /// # Returns
     /// * `RustyResult<Albums>`: On success, returns an `Albums` object containing detailed
-    ///   information about each requested album. On failure, returns a `RustyError` detailing
-    ///   the issue, such as exceeding the maximum number of IDs allowed.
+    ///   information about each requested album, in the order of `album_ids`. Albums Spotify could
+    ///   not find are left out. On failure, returns a `RustyError` detailing the issue.
     ///
     /// # Errors
-    /// * Returns an error if the provided list of album IDs is empty or exceeds 20, as this is
-    ///   the Spotify API's limit for this type of request.
-    /// * Returns a `RustyError::InvalidInput` for invalid input parameters.
+    /// * Returns a `RustyError::InvalidInput` if the provided list of album IDs is empty.
     ///
     /// # Caching
     /// * The method optimizes data fetching by leveraging a caching mechanism. It checks the cache
     ///   for each requested album ID and uses the cached data if available and not expired.
-    /// * For any missing or expired albums, it fetches the data for all requested albums from the
-    ///   Spotify API and updates the cache accordingly.
+    /// * Only the missing or expired albums are fetched from the Spotify API, and the cache is
+    ///   updated accordingly.
+    ///
+    /// # Batching
+    /// * The uncached IDs are split into requests of at most 20 IDs, Spotify's limit for this
+    ///   endpoint, and up to 4 of these requests are sent concurrently.
     ///
     /// # Example
     /// ```
     /// # use rustyspoty::SpotifyClientCredentials;
     /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
-    /// # let mut client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// # let client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
     /// let album_ids = ["1o2NpYGqHiCq7FoiYdyd1x".to_string(), "4tZwfgrHOc3mvqYlEYSvVi".to_string()];
     /// let result = client.get_several_albums(&album_ids).await;
     /// if let Ok(albums_response) = result {
@@ -281,118 +1227,216 @@ impl SpotifyClientCredentials {
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn get_several_albums(&mut self, album_ids: &[String]) -> RustyResult<Albums> {
+    pub async fn get_several_albums(&self, album_ids: &[String]) -> RustyResult<Albums> {
         if album_ids.is_empty() {
             return Err(RustyError::invalid_input("Please provide at least 1 album ID."));
         }
-        if album_ids.len() > 20 {
-            return Err(RustyError::invalid_input("Maximum of 20 IDs."));
-        }
 
+        let mut albums: Vec<Option<Album>> = Vec::with_capacity(album_ids.len());
         let mut albums_to_fetch = Vec::new();
-        let mut albums_from_cache = Vec::new();
 
         // Check cache first
         for id in album_ids {
             let cache_key = format!("/albums/{id}");
             if let Some(cached_album) = self.check_cache(&cache_key).await {
-                albums_from_cache.push(serde_json::from_value::<Album>(cached_album)?);
+                albums.push(Some(serde_json::from_value::<Album>(cached_album)?));
             } else {
+                albums.push(None);
                 albums_to_fetch.push(id.clone());
             }
         }
 
-        // If all albums were found in cache, return them directly
-        if albums_to_fetch.is_empty() {
-            return Ok(Albums { albums: albums_from_cache });
-        }
-
         // Fetch missing albums from Spotify API
-        let ids_param = album_ids.join(",");
-        let path = format!("/albums?ids={}", ids_param);
-        let fetched_albums: Albums = self.get_spotify_data(&path).await?;
-
-        // Update cache with fetched albums
-        for album in &fetched_albums.albums {
+        let fetched: Vec<Albums> = self.fetch_in_chunks(
+            &albums_to_fetch,
+            MAX_ALBUM_IDS,
+            |ids| format!("/albums?ids={ids}")
+        ).await?;
+        let mut fetched_albums: HashMap<String, Album> = HashMap::new();
+        for album in fetched.into_iter().flat_map(|response| response.albums) {
             let cache_key = format!("/albums/{}", album.id);
-            self.update_cache(cache_key, serde_json::to_value(album)?).await;
+            self.update_cache(cache_key, serde_json::to_value(&album)?).await;
+            fetched_albums.insert(album.id.clone(), album);
+        }
+
+        // Fill the gaps left by the cache in request order, dropping the albums Spotify could not find
+        for (id, slot) in album_ids.iter().zip(albums.iter_mut()) {
+            if slot.is_none() {
+                *slot = fetched_albums.get(id).cloned();
+            }
         }
 
-        // Combine cached albums with fetched albums before returning
-        let combined_albums = [albums_from_cache, fetched_albums.albums].concat();
-        Ok(Albums { albums: combined_albums })
+        Ok(Albums { albums: albums.into_iter().flatten().collect() })
     }
 
-    /// Retrieves the tracks contained in a specific album on Spotify.
+    /// Fetches several albums like [`get_several_albums`](Self::get_several_albums), keyed by
+    /// Spotify ID.
+    ///
+    /// IDs that Spotify could not resolve are simply absent from the map.
+    ///
+    /// # Arguments
+    /// * `album_ids`: A slice of Spotify album IDs.
+    ///
+    /// # Example
+    /// ```
+    /// # use rustyspoty::SpotifyClientCredentials;
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// let album_ids = ["1o2NpYGqHiCq7FoiYdyd1x".to_string(), "4tZwfgrHOc3mvqYlEYSvVi".to_string()];
+    /// let albums = client.get_albums_map(&album_ids).await?;
+    /// if let Some(album) = albums.get("1o2NpYGqHiCq7FoiYdyd1x") {
+    ///     println!("Album name: {}", album.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_albums_map(&self, album_ids: &[String]) -> RustyResult<HashMap<String, Album>> {
+        let response = self.get_several_albums(album_ids).await?;
+        Ok(
+            response.albums
+                .into_iter()
+                .map(|album| (album.id.clone(), album))
+                .collect()
+        )
+    }
+
+    /// Fetches several albums like [`get_albums_map`](Self::get_albums_map), also reporting the
+    /// requested IDs Spotify returned no album for.
+    ///
+    /// # Arguments
+    /// * `album_ids`: A slice of Spotify album IDs.
+    pub async fn get_several_albums_detailed(
+        &self,
+        album_ids: &[String]
+    ) -> RustyResult<BatchResult<Album>> {
+        let found = self.get_albums_map(album_ids).await?;
+        Ok(BatchResult::new(album_ids, found))
+    }
+
+    /// Retrieves a page of the tracks contained in a specific album on Spotify.
     ///
     /// This function is ideal for applications that need to display track listings for albums, such as music library managers or playlist creators.
     ///
     /// # Arguments
     /// * `album_id`: The unique identifier for the album on Spotify.
+    /// * `limit`: The maximum number of tracks to return, clamped to 1-50. Defaults to 20.
+    /// * `offset`: The index of the first track to return. Defaults to 0.
+    /// * `market`: An optional ISO 3166-1 alpha-2 country code. Tracks are then relinked for that
+    ///   market, see `SimplifiedTrack::linked_from` and `SimplifiedTrack::is_playable`. Falls back
+    ///   to the client's default market when `None`.
     ///
     /// # Returns
-    /// * `Result<AlbumTracks, RustyError>`: On success, returns an `AlbumTracks` object containing a list of tracks in the specified album. On failure, returns a `RustyError` detailing the issue encountered during the API call.
+    /// * `Result<Page<SimplifiedTrack>, RustyError>`: On success, returns a page of the tracks in the specified album. Use `get_all_album_tracks` to fetch every page. On failure, returns a `RustyError` detailing the issue encountered during the API call.
     ///
     /// # Errors
     /// * An error will be returned if the album ID is invalid, if there's a problem with the network request, or if the API responds with an error.
     ///
     /// # Example
     /// ```
-    /// # use rustyspoty::SpotifyClientCredentials;
+    /// # use rustyspoty::{ models::market::Market, SpotifyClientCredentials };
     /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
-    /// # let mut client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// # let client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
     /// let album_id = "4aawyAB9vmqN3uQ7FjRGTy";
-    /// let result = client.get_album_tracks(album_id).await;
+    /// let result = client.get_album_tracks(album_id, Some(10), None, Some(Market::US)).await;
     /// if let Ok(album_tracks) = result {
     ///     for track in album_tracks.items {
-    ///         println!("Track: {}", track.name);
+    ///         println!("Track: {} (playable: {:?})", track.name, track.is_playable);
     ///     }
     /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn get_album_tracks(&mut self, album_id: &str) -> RustyResult<Page<SimplifiedTrack>> {
-        let path = format!("/albums/{album_id}/tracks");
+    pub async fn get_album_tracks(
+        &self,
+        album_id: &str,
+        limit: Option<u32>,
+        offset: Option<u32>,
+        market: Option<Market>
+    ) -> RustyResult<Page<SimplifiedTrack>> {
+        let limit = limit.unwrap_or(20).clamp(1, 50); // Ensures limit is within 1-50
+        let offset = offset.unwrap_or(0);
+        let market = self.resolve_market(market)?;
+
+        let params = serde_json::json!({ "limit": limit, "offset": offset, "market": market });
+        let path = format!("/albums/{album_id}/tracks?{}", self.to_query_string(&params));
         self.get_spotify_data(&path).await
     }
 
-    /// Fetches detailed information about a specific album from Spotify.
+    /// Retrieves all tracks of an album, following the pages of results.
+    ///
+    /// `Album::tracks` only holds the first page of an album's tracks and `get_album_tracks` a
+    /// single page, which truncates box sets and compilations with more than 50 tracks.
+    ///
+    /// # Arguments
+    /// * `album_id` - The Spotify ID of the album.
+    /// * `market` - An optional ISO 3166-1 alpha-2 country code, to relink the tracks to that
+    ///   market. Falls back to the client's default market when `None`.
+    ///
+    /// # Returns
+    /// * `RustyResult<Vec<SimplifiedTrack>>`: The album's tracks, in album order. At most 5000
+    ///   tracks are returned, and a `next` link that does not advance ends the fetch, guarding
+    ///   against runaway fetches. An error on any page is returned as is.
+    ///
+    /// # Caching
+    /// * Each page is cached separately.
     ///
-    /// This function retrieves all available data for a given album, identified by its unique Spotify ID. This includes tracks, artists, release date, and more, which can be useful for applications that require detailed album metadata.
+    /// # Example
+    /// ```
+    /// # use rustyspoty::SpotifyClientCredentials;
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// let tracks = client.get_all_album_tracks("4aawyAB9vmqN3uQ7FjRGTy", None).await?;
+    /// println!("{} tracks", tracks.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_all_album_tracks(
+        &self,
+        album_id: &str,
+        market: Option<Market>
+    ) -> RustyResult<Vec<SimplifiedTrack>> {
+        let mut path = format!("/albums/{album_id}/tracks?limit={MAX_PAGE_LIMIT}");
+        if let Some(market) = self.resolve_market(market)? {
+            path.push_str(&format!("&market={market}"));
+        }
+        self.fetch_all(&path, MAX_PAGINATED_ITEMS).await
+    }
+
+    /// Fetches a list of new album releases featured in Spotify, as shown on the browse tab.
     ///
     /// # Arguments
-    /// * `album_id`: A `&str` representing the Spotify ID of the album to retrieve.
+    /// * `limit`: The maximum number of albums to return, clamped to 1-50. Defaults to 20.
+    /// * `offset`: The index of the first album to return, at least 0. Defaults to 0.
+    ///
+    /// Spotify serves at most the first 1000 new releases: `offset + limit` must not exceed 1000.
     ///
     /// # Returns
-    /// * `Result<Album, RustyError>`: On success, returns an `Album` object containing detailed information about the specified album. On failure, returns a `RustyError` indicating what went wrong during the request.
+    /// * `Result<NewAlbums, RustyError>`: On success, returns a `NewAlbums` object wrapping a page of simplified albums. On failure, returns a `RustyError` indicating what went wrong during the request.
     ///
     /// # Errors
-    /// * An error is returned if the specified `album_id` does not exist or if there is a network issue that prevents the API call from completing successfully.
+    /// Returns `RustyError::Io` with `InvalidInput`, without sending a request, if `offset + limit`
+    /// exceeds 1000.
     ///
     /// # Example
     /// ```
     /// # use rustyspoty::SpotifyClientCredentials;
     /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
-    /// # let mut client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
-    /// let album_id = "3ThQkHrQ6FSq8VIBv3WIEs";
-    /// let result = client.get_album(album_id).await;
-    /// match result {
-    ///     Ok(album) => println!("Album found: {}", album.name),
-    ///     Err(e) => eprintln!("An error occurred: {}", e),
+    /// # let client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// let new_releases = client.get_new_album_releases(Some(10), None).await?;
+    /// for album in new_releases.albums.items {
+    ///     println!("New release: {}", album.name);
     /// }
     /// # Ok(())
     /// # }
     /// ```
-    ///
-    /// This method provides an efficient way to access detailed album information,
-    /// including links to high-quality cover art, making it essential for music-related applications and servicess.
     pub async fn get_new_album_releases(
-        &mut self,
+        &self,
         limit: Option<i32>,
         offset: Option<i32>
     ) -> RustyResult<NewAlbums> {
-        let limit = limit.unwrap_or(20).min(50).max(1); // Ensures limit is within 1-50
+        let limit = limit.unwrap_or(20).clamp(1, 50); // Ensures limit is within 1-50
         let offset = offset.unwrap_or(0).max(0); // Ensures offset is non-negative
+        validate_offset(offset as usize, limit as usize)?;
 
         let query_params = format!("?limit={}&offset={}", limit, offset);
         let path = format!("/browse/new-releases{}", query_params);
@@ -400,10 +1444,199 @@ impl SpotifyClientCredentials {
         self.get_spotify_data::<NewAlbums>(&path).await
     }
 
+    /// Fetches a page of the categories used to tag items in Spotify, as shown on the browse tab.
+    ///
+    /// # Arguments
+    /// * `country`: An optional ISO 3166-1 alpha-2 country code, to get the categories relevant
+    ///   to that country.
+    /// * `locale`: An optional language and country code, such as `"es_MX"`, to get the category
+    ///   names in that language. Defaults to American English.
+    /// * `limit`: The maximum number of categories to return, clamped to 1-50. Defaults to 20.
+    /// * `offset`: The index of the first category to return, at least 0. Defaults to 0.
+    ///
+    /// Spotify serves at most the first 1000 categories: `offset + limit` must not exceed 1000.
+    ///
+    /// # Returns
+    /// * `RustyResult<Categories>`: A page of categories.
+    ///
+    /// # Caching
+    /// * Categories rarely change, so they are cached for 6 hours.
+    ///
+    /// # Example
+    /// ```
+    /// # use rustyspoty::SpotifyClientCredentials;
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// let categories = client.get_categories(Some("SE"), Some("sv_SE"), Some(10), None).await?;
+    /// for category in categories.categories.items {
+    ///     println!("Category: {}", category.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_categories(
+        &self,
+        country: Option<&str>,
+        locale: Option<&str>,
+        limit: Option<i32>,
+        offset: Option<i32>
+    ) -> RustyResult<Categories> {
+        let limit = limit.unwrap_or(20).clamp(1, 50); // Ensures limit is within 1-50
+        let offset = offset.unwrap_or(0).max(0); // Ensures offset is non-negative
+        validate_offset(offset as usize, limit as usize)?;
+
+        let params =
+            serde_json::json!({
+            "country": country,
+            "locale": locale,
+            "limit": limit,
+            "offset": offset,
+        });
+        let path = format!("/browse/categories?{}", self.to_query_string(&params));
+        self.get_spotify_data(&path).await
+    }
+
+    /// Fetches a single category used to tag items in Spotify.
+    ///
+    /// # Arguments
+    /// * `category_id`: The Spotify category ID, e.g. `"dinner"`.
+    /// * `country`: An optional ISO 3166-1 alpha-2 country code, to make sure the category exists
+    ///   in that country.
+    /// * `locale`: An optional language and country code, such as `"es_MX"`, to get the category
+    ///   name in that language.
+    ///
+    /// # Returns
+    /// * `RustyResult<Category>`: The category.
+    ///
+    /// # Example
+    /// ```
+    /// # use rustyspoty::SpotifyClientCredentials;
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// let category = client.get_category("dinner", None, Some("es_MX")).await?;
+    /// println!("Category: {}", category.name);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_category(
+        &self,
+        category_id: &str,
+        country: Option<&str>,
+        locale: Option<&str>
+    ) -> RustyResult<Category> {
+        let params = serde_json::json!({ "country": country, "locale": locale });
+        let query = self.to_query_string(&params);
+        let path = if query.is_empty() {
+            format!("/browse/categories/{category_id}")
+        } else {
+            format!("/browse/categories/{category_id}?{query}")
+        };
+        self.get_spotify_data(&path).await
+    }
+
+    /// Fetches a page of the playlists featured by Spotify, as shown on the browse tab.
+    ///
+    /// # Arguments
+    /// * `country`: An optional ISO 3166-1 alpha-2 country code, to get the playlists featured in
+    ///   that country.
+    /// * `locale`: An optional language and country code, such as `"es_MX"`, to get the message
+    ///   in that language.
+    /// * `timestamp`: An optional ISO 8601 timestamp in the user's local time, such as
+    ///   `"2014-10-23T09:00:00"`, to get the playlists featured at that time of day.
+    /// * `limit`: The maximum number of playlists to return, clamped to 1-50. Defaults to 20.
+    /// * `offset`: The index of the first playlist to return, at least 0. Defaults to 0.
+    ///
+    /// Spotify serves at most the first 1000 playlists: `offset + limit` must not exceed 1000.
+    ///
+    /// # Returns
+    /// * `RustyResult<FeaturedPlaylists>`: The featured playlists and their headline.
+    ///
+    /// # Example
+    /// ```
+    /// # use rustyspoty::SpotifyClientCredentials;
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// let featured = client.get_featured_playlists(Some("SE"), None, None, Some(10), None).await?;
+    /// println!("{}", featured.message);
+    /// for playlist in featured.playlists.items {
+    ///     println!("Playlist: {}", playlist.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_featured_playlists(
+        &self,
+        country: Option<&str>,
+        locale: Option<&str>,
+        timestamp: Option<&str>,
+        limit: Option<i32>,
+        offset: Option<i32>
+    ) -> RustyResult<FeaturedPlaylists> {
+        let limit = limit.unwrap_or(20).clamp(1, 50); // Ensures limit is within 1-50
+        let offset = offset.unwrap_or(0).max(0); // Ensures offset is non-negative
+        validate_offset(offset as usize, limit as usize)?;
+
+        let params =
+            serde_json::json!({
+            "country": country,
+            "locale": locale,
+            "timestamp": timestamp,
+            "limit": limit,
+            "offset": offset,
+        });
+        let path = format!("/browse/featured-playlists?{}", self.to_query_string(&params));
+        self.get_spotify_data(&path).await
+    }
+
+    /// Fetches a page of the playlists tagged with a category.
+    ///
+    /// # Arguments
+    /// * `category_id`: The Spotify category ID, e.g. `"dinner"`.
+    /// * `country`: An optional ISO 3166-1 alpha-2 country code, to get the playlists available
+    ///   in that country.
+    /// * `limit`: The maximum number of playlists to return, clamped to 1-50. Defaults to 20.
+    /// * `offset`: The index of the first playlist to return, at least 0. Defaults to 0.
+    ///
+    /// Spotify serves at most the first 1000 playlists: `offset + limit` must not exceed 1000.
+    ///
+    /// # Returns
+    /// * `RustyResult<FeaturedPlaylists>`: The category's playlists.
+    ///
+    /// # Example
+    /// ```
+    /// # use rustyspoty::SpotifyClientCredentials;
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// let playlists = client.get_category_playlists("dinner", None, Some(10), None).await?;
+    /// for playlist in playlists.playlists.items {
+    ///     println!("Playlist: {}", playlist.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_category_playlists(
+        &self,
+        category_id: &str,
+        country: Option<&str>,
+        limit: Option<i32>,
+        offset: Option<i32>
+    ) -> RustyResult<FeaturedPlaylists> {
+        let limit = limit.unwrap_or(20).clamp(1, 50); // Ensures limit is within 1-50
+        let offset = offset.unwrap_or(0).max(0); // Ensures offset is non-negative
+        validate_offset(offset as usize, limit as usize)?;
+
+        let params = serde_json::json!({ "country": country, "limit": limit, "offset": offset });
+        let path = format!(
+            "/browse/categories/{category_id}/playlists?{}",
+            self.to_query_string(&params)
+        );
+        self.get_spotify_data(&path).await
+    }
+
     /// Fetches detailed information about a specific artist from the Spotify API.
     ///
     /// # Arguments
-    /// * `artist_id` - A `&str` slice that holds the Spotify ID of the artist.
+    /// * `artist_id` - The Spotify ID, URI or `open.spotify.com` URL of the artist.
     ///
     /// # Returns
     /// `Result<Artist, RustyError>`
@@ -414,17 +1647,27 @@ impl SpotifyClientCredentials {
     /// ```
     /// # use rustyspoty::SpotifyClientCredentials;
     /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
-    /// # let mut client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// # let client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
     /// let artist = client.get_artist("artist_id").await?;
     /// println!("Artist Name: {}", artist.name);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn get_artist(&mut self, artist_id: &str) -> RustyResult<Artist> {
-        let path = format!("/artists/{artist_id}");
+    pub async fn get_artist(&self, artist_id: &str) -> RustyResult<Artist> {
+        let path = format!("/artists/{}", normalize_id(artist_id, "artist")?);
         self.get_spotify_data(&path).await
     }
 
+    /// Fetches an artist like [`get_artist`](Self::get_artist), also reporting whether it was
+    /// served from the cache and when it was fetched from Spotify.
+    ///
+    /// # Arguments
+    /// * `artist_id` - The Spotify ID, URI or `open.spotify.com` URL of the artist.
+    pub async fn get_artist_cached(&self, artist_id: &str) -> RustyResult<Cached<Artist>> {
+        let path = format!("/artists/{}", normalize_id(artist_id, "artist")?);
+        self.get_spotify_data_cached(&path).await
+    }
+
     /// Retrieves information for multiple artists based on their Spotify IDs.
     ///
     /// This method first checks if the requested artist information is available in the cache
@@ -454,7 +1697,7 @@ impl SpotifyClientCredentials {
     /// ```
     /// # use rustyspoty::SpotifyClientCredentials;
     /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
-    /// # let mut client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// # let client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
     /// let artist_ids = vec!["artist_id1".to_string(), "artist_id2".to_string()];
     /// let artists = client.get_several_artists(&artist_ids).await?;
     /// for artist in artists.artists {
@@ -463,7 +1706,7 @@ impl SpotifyClientCredentials {
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn get_several_artists(&mut self, artist_ids: &[String]) -> RustyResult<Artists> {
+    pub async fn get_several_artists(&self, artist_ids: &[String]) -> RustyResult<Artists> {
         if artist_ids.is_empty() {
             return Err(RustyError::invalid_input("Please provide at least 1 artist ID."));
         }
@@ -505,24 +1748,80 @@ impl SpotifyClientCredentials {
         Ok(Artists { artists: combined_artists })
     }
 
-    /// Retrieves the albums associated with a specific artist from the Spotify catalog.
+    /// Fetches several artists like [`get_several_artists`](Self::get_several_artists), keyed
+    /// by Spotify ID.
+    ///
+    /// IDs that Spotify could not resolve are simply absent from the map.
+    ///
+    /// # Arguments
+    /// * `artist_ids` - A slice of Spotify artist IDs, up to 50.
+    ///
+    /// # Example
+    /// ```
+    /// # use rustyspoty::SpotifyClientCredentials;
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// let artist_ids = ["0TnOYISbd1XYRBk9myaseg".to_string()];
+    /// let artists = client.get_artists_map(&artist_ids).await?;
+    /// if let Some(artist) = artists.get("0TnOYISbd1XYRBk9myaseg") {
+    ///     println!("Artist name: {}", artist.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_artists_map(
+        &self,
+        artist_ids: &[String]
+    ) -> RustyResult<HashMap<String, Artist>> {
+        let response = self.get_several_artists(artist_ids).await?;
+        Ok(
+            response.artists
+                .into_iter()
+                .map(|artist| (artist.id.clone(), artist))
+                .collect()
+        )
+    }
+
+    /// Fetches several artists like [`get_artists_map`](Self::get_artists_map), also reporting
+    /// the requested IDs Spotify returned no artist for.
+    ///
+    /// # Arguments
+    /// * `artist_ids` - A slice of Spotify artist IDs, up to 50.
+    pub async fn get_several_artists_detailed(
+        &self,
+        artist_ids: &[String]
+    ) -> RustyResult<BatchResult<Artist>> {
+        let found = self.get_artists_map(artist_ids).await?;
+        Ok(BatchResult::new(artist_ids, found))
+    }
+
+    /// Retrieves a page of the albums associated with a specific artist from the Spotify catalog.
     ///
     /// # Arguments
     /// * `artist_id` - The Spotify ID of the artist whose albums are being retrieved.
+    /// * `include_groups` - The album groups to include, e.g. only full albums, leaving out singles
+    ///   and the albums the artist appears on. All groups are included when `None` or empty.
+    /// * `market` - An optional ISO 3166-1 alpha-2 country code, to only include albums available
+    ///   in that market. Falls back to the client's default market when `None`.
+    /// * `limit` - The maximum number of albums to return, clamped to 1-50. Defaults to 20.
+    /// * `offset` - The index of the first album to return. Defaults to 0.
     ///
     /// # Returns
-    /// * `Result<ArtistAlbumsResponse, Box<dyn Error>>`: On success, returns an `ArtistAlbumsResponse` containing the artist's albums. On failure, returns a boxed error detailing the failure reason.
+    /// * `RustyResult<Page<SimplifiedAlbum>>`: On success, returns a page of the artist's albums. Use `get_all_artist_albums` to fetch every page. On failure, returns a `RustyError` detailing the failure reason.
     ///
     /// # Errors
     /// * Returns an error for invalid artist ID, network issues, or Spotify API errors.
     ///
+    /// # Caching
+    /// * Each combination of groups, market and page is cached separately.
+    ///
     /// # Example
     /// ```
-    /// # use rustyspoty::SpotifyClientCredentials;
+    /// # use rustyspoty::{ models::album::AlbumGroup, SpotifyClientCredentials };
     /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
-    /// # let mut spotify_client = SpotifyClientCredentials::new("your_client_id".to_string(), "your_client_secret".to_string());
+    /// # let spotify_client = SpotifyClientCredentials::new("your_client_id".to_string(), "your_client_secret".to_string());
     /// let artist_id = "4tZwfgrHOc3mvqYlEYSvVi"; // Example artist ID for Daft Punk
-    /// match spotify_client.get_artist_albums(artist_id).await {
+    /// match spotify_client.get_artist_albums(artist_id, Some(&[AlbumGroup::Album]), None, Some(50), None).await {
     ///     Ok(response) => {
     ///         for album in response.items {
     ///             println!("Album: {} - Release Date: {}", album.name, album.release_date);
@@ -534,33 +1833,104 @@ impl SpotifyClientCredentials {
     /// # }
     /// ```
     pub async fn get_artist_albums(
-        &mut self,
-        artist_id: &str
+        &self,
+        artist_id: &str,
+        include_groups: Option<&[AlbumGroup]>,
+        market: Option<Market>,
+        limit: Option<u32>,
+        offset: Option<u32>
     ) -> RustyResult<Page<SimplifiedAlbum>> {
-        let path = format!("/artists/{artist_id}/albums");
+        let include_groups = include_groups
+            .filter(|groups| !groups.is_empty())
+            .map(|groups| groups.iter().map(AlbumGroup::as_str).collect::<Vec<&str>>());
+        let limit = limit.unwrap_or(20).clamp(1, 50); // Ensures limit is within 1-50
+        let offset = offset.unwrap_or(0);
+        let market = self.resolve_market(market)?;
+
+        let params =
+            serde_json::json!({
+            "include_groups": include_groups,
+            "limit": limit,
+            "market": market,
+            "offset": offset
+        });
+        let path = format!("/artists/{artist_id}/albums?{}", self.to_query_string(&params));
         self.get_spotify_data(&path).await
     }
 
-    /// Fetches an artist's top tracks from the Spotify catalog, optionally filtered by a specific market.
+    /// Retrieves all albums associated with a specific artist, following the pages of results.
     ///
     /// # Arguments
-    ///
-    /// * `artist_id` - A `&str` slice representing the Spotify ID of the artist.
-    /// * `market` - An optional `&str` slice representing an ISO 3166-1 alpha-2 country code to filter tracks available in a specific market.
+    /// * `artist_id` - The Spotify ID of the artist whose albums are being retrieved.
+    /// * `include_groups` - The album groups to include, e.g. only albums and singles. All groups
+    ///   are included when empty.
+    /// * `market` - An optional ISO 3166-1 alpha-2 country code, to only include albums available
+    ///   in that market. Falls back to the client's default market when `None`.
     ///
     /// # Returns
+    /// * `RustyResult<Vec<SimplifiedAlbum>>`: The artist's albums, in the order Spotify lists them.
+    ///   At most 5000 albums are returned, guarding against runaway fetches.
     ///
-    /// * `Result<ArtistTopTracksResponse, RustyError>`: On success, this function returns an `ArtistTopTracksResponse` containing the artist's top tracks. On failure, it returns a `RustyError` detailing the issue encountered.
+    /// # Caching
+    /// * Each page is cached separately, like the result of `get_artist_albums`.
     ///
     /// # Example
-    ///
     /// ```
-    /// # use rustyspoty::SpotifyClientCredentials;
+    /// # use rustyspoty::{ models::{ album::AlbumGroup, market::Market }, SpotifyClientCredentials };
     /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
-    /// # let mut spotify_client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
-    /// let artist_id = "0TnOYISbd1XYRBk9myaseg";
-    /// let market = Some("US");
-    /// let top_tracks = spotify_client.get_artist_top_tracks(artist_id, market).await?;
+    /// # let spotify_client = SpotifyClientCredentials::new("your_client_id".to_string(), "your_client_secret".to_string());
+    /// let artist_id = "4tZwfgrHOc3mvqYlEYSvVi";
+    /// let albums = spotify_client
+    ///     .get_all_artist_albums(artist_id, &[AlbumGroup::Album, AlbumGroup::Single], Some(Market::US)).await?;
+    /// println!("{} albums and singles", albums.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_all_artist_albums(
+        &self,
+        artist_id: &str,
+        include_groups: &[AlbumGroup],
+        market: Option<Market>
+    ) -> RustyResult<Vec<SimplifiedAlbum>> {
+        let mut path = format!("/artists/{artist_id}/albums?limit={MAX_PAGE_LIMIT}");
+        if !include_groups.is_empty() {
+            let groups: Vec<&str> = include_groups
+                .iter()
+                .map(AlbumGroup::as_str)
+                .collect();
+            path.push_str(&format!("&include_groups={}", groups.join(",")));
+        }
+        if let Some(market) = self.resolve_market(market)? {
+            path.push_str(&format!("&market={market}"));
+        }
+        self.fetch_all(&path, MAX_PAGINATED_ITEMS).await
+    }
+
+    /// Fetches an artist's top tracks from the Spotify catalog, optionally filtered by a specific market.
+    ///
+    /// # Arguments
+    ///
+    /// * `artist_id` - A `&str` slice representing the Spotify ID of the artist.
+    /// * `market` - An optional `&str` slice representing an ISO 3166-1 alpha-2 country code to filter tracks available in a specific market.
+    ///   Falls back to the client's default market when `None`; `from_token` is rejected.
+    ///
+    /// Spotify requires a market for this endpoint. When neither `market` nor the client's default
+    /// market is set, the top tracks are requested for `US`, or for the user's own market
+    /// (`from_token`) when the client is authorized on behalf of a user.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<ArtistTopTracksResponse, RustyError>`: On success, this function returns an `ArtistTopTracksResponse` containing the artist's top tracks. On failure, it returns a `RustyError` detailing the issue encountered.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rustyspoty::{ models::market::Market, SpotifyClientCredentials };
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let spotify_client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// let artist_id = "0TnOYISbd1XYRBk9myaseg";
+    /// let market = Some(Market::US);
+    /// let top_tracks = spotify_client.get_artist_top_tracks(artist_id, market).await?;
     /// for track in top_tracks.tracks {
     ///     println!("Track name: {}", track.name);
     /// }
@@ -568,12 +1938,15 @@ impl SpotifyClientCredentials {
     /// # }
     /// ```
     pub async fn get_artist_top_tracks(
-        &mut self,
+        &self,
         artist_id: &str,
-        market: Option<&str>
+        market: Option<Market>
     ) -> RustyResult<TracksResponse> {
-        let market_query = market.map_or(String::new(), |m| format!("?market={}", m));
-        let path = format!("/artists/{}/top-tracks{}", artist_id, market_query);
+        let market = self.resolve_market(market)?.unwrap_or_else(|| {
+            let market = if self.token_manager.is_user() { Market::FromToken } else { DEFAULT_TOP_TRACKS_MARKET };
+            market.to_string()
+        });
+        let path = format!("/artists/{}/top-tracks?market={}", artist_id, market);
         self.get_spotify_data::<TracksResponse>(&path).await
     }
 
@@ -591,7 +1964,7 @@ impl SpotifyClientCredentials {
     /// ```
     /// # use rustyspoty::SpotifyClientCredentials;
     /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
-    /// # let mut client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// # let client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
     /// let artist_id = "3TVXtAsR1Inumwj472S9r4";
     /// let related_artists = client.get_related_artists(artist_id).await?;
     /// println!("Related Artists: {:?}", related_artists);
@@ -599,7 +1972,7 @@ impl SpotifyClientCredentials {
     /// # }
     /// ```
     /// This function helps users explore the music landscape by introducing them to artists similar to their favorites.
-    pub async fn get_related_artists(&mut self, artist_id: &str) -> Result<Artists, RustyError> {
+    pub async fn get_related_artists(&self, artist_id: &str) -> Result<Artists, RustyError> {
         let path: String = format!("/artists/{}/related-artists", artist_id);
         self.get_spotify_data(&path).await
     }
@@ -614,22 +1987,48 @@ impl SpotifyClientCredentials {
     /// ```
     /// # use rustyspoty::SpotifyClientCredentials;
     /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
-    /// # let mut client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// # let client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
     /// let genre_seeds = client.get_genre_seeds().await?;
     /// println!("Available Genre Seeds: {:?}", genre_seeds);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn get_genre_seeds(&mut self) -> Result<GenreSeedsResponse, RustyError> {
+    pub async fn get_genre_seeds(&self) -> Result<GenreSeedsResponse, RustyError> {
         let path = "/recommendations/available-genre-seeds";
         // Use the `get_spotify_data` method to make the request, specifying GenreSeedsResponse as the type parameter
         self.get_spotify_data::<GenreSeedsResponse>(path).await
     }
 
+    /// Fetches the markets where Spotify is available.
+    ///
+    /// The list rarely changes, so it is cached for several hours. Use it to check a market code
+    /// before passing it to methods such as `get_artist_top_tracks`.
+    ///
+    /// # Returns
+    ///
+    /// * `RustyResult<Vec<String>>`: The ISO 3166-1 alpha-2 country codes of the markets.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rustyspoty::{ models::market::Market, SpotifyClientCredentials };
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// let markets = client.get_available_markets().await?;
+    /// if markets.iter().any(|market| market == "SE") {
+    ///     let top_tracks = client.get_artist_top_tracks("0TnOYISbd1XYRBk9myaseg", Some(Market::SE)).await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_available_markets(&self) -> RustyResult<Vec<String>> {
+        let response: MarketsResponse = self.get_spotify_data("/markets").await?;
+        Ok(response.markets)
+    }
+
     /// Fetches detailed information about a specific track from the Spotify API.
     ///
     /// # Arguments
-    /// * `track_id` - The Spotify ID of the track.
+    /// * `track_id` - The Spotify ID, URI or `open.spotify.com` URL of the track.
     ///
     /// # Returns
     /// * `Result<Track, RustyError>` - On success, returns the track's detailed information wrapped
@@ -639,15 +2038,15 @@ impl SpotifyClientCredentials {
     /// ```
     /// # use rustyspoty::SpotifyClientCredentials;
     /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
-    /// # let mut client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// # let client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
     /// let track_id = "11dFghVXANMlKmJXsNCbNl";
     /// let track = client.get_track(track_id).await?;
     /// println!("Track Name: {}", track.name);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn get_track(&mut self, track_id: &str) -> Result<Track, RustyError> {
-        let path = format!("/tracks/{track_id}");
+    pub async fn get_track(&self, track_id: &str) -> Result<Track, RustyError> {
+        let path = format!("/tracks/{}", normalize_id(track_id, "track")?);
         self.get_spotify_data(&path).await
     }
 
@@ -657,6 +2056,7 @@ impl SpotifyClientCredentials {
     /// # Arguments
     /// * `track_ids` - A slice of Spotify IDs for the tracks.
     /// * `market` - An optional market code to filter tracks available in a specific market.
+    ///   Falls back to the client's default market when `None`; `from_token` is rejected.
     ///
     /// # Returns
     /// * `RustyResult<TracksResponse>`: On success, returns a `TracksResponse` object containing detailed
@@ -665,65 +2065,320 @@ impl SpotifyClientCredentials {
     /// # Caching
     /// * Checks the cache for each requested track ID and uses cached data if available and valid.
     /// * Updates the cache with new data fetched from the Spotify API for missing or expired tracks.
+    /// * Entries are kept per market. A track relinked by Spotify is cached under both the requested
+    ///   ID and the ID it was relinked to.
+    ///
+    /// # Batching
+    /// * The uncached IDs are split into requests of at most 50 IDs, Spotify's limit for this
+    ///   endpoint, and up to 4 of these requests are sent concurrently. The tracks are returned in
+    ///   the order of `track_ids`, leaving out the tracks Spotify could not find.
+    ///
+    /// # Markets
+    /// * Passing a `market` makes Spotify relink unplayable tracks to a playable version where one
+    ///   exists; it does not remove anything from the response.
+    /// * To drop tracks that cannot be played in a region, call
+    ///   [`TracksResponse::only_available_in`] on the result instead.
     ///
     /// # Example
     /// ```
-    /// # use rustyspoty::SpotifyClientCredentials;
+    /// # use rustyspoty::{ models::market::Market, SpotifyClientCredentials };
     /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
-    /// # let mut client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// # let client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
     /// let track_ids = vec!["track_id1".to_string(), "track_id2".to_string()];
-    /// let tracks = client.get_several_tracks(&track_ids, Some("US")).await?;
+    /// let tracks = client.get_several_tracks(&track_ids, Some(Market::US)).await?;
     /// for track in tracks.tracks {
     ///     println!("Track name: {}", track.name);
     /// }
+    ///
+    /// // Keep only the tracks that are playable in Japan.
+    /// let playable = client.get_several_tracks(&track_ids, None).await?.only_available_in("JP");
     /// # Ok(())
     /// # }
     /// ```
     pub async fn get_several_tracks(
-        &mut self,
+        &self,
         track_ids: &[String],
-        market: Option<&str>
+        market: Option<Market>
     ) -> RustyResult<TracksResponse> {
         if track_ids.is_empty() {
             return Err(RustyError::invalid_input("Please provide at least 1 track ID."));
         }
-        if track_ids.len() > 20 {
-            return Err(RustyError::invalid_input("Maximum of 20 IDs."));
-        }
 
+        let market = self.resolve_market(market)?;
+        let market = market.as_deref();
         let market_query = market.map_or(String::new(), |m| format!("&market={}", m));
+        let mut tracks: Vec<Option<Track>> = Vec::with_capacity(track_ids.len());
         let mut tracks_to_fetch = Vec::new();
-        let mut tracks_from_cache: Vec<Track> = Vec::new();
 
         // Check cache first
         for id in track_ids {
-            let cache_key = format!("/tracks/{id}{market_query}");
+            let cache_key = track_cache_key(id, market);
             if let Some(cached_track) = self.check_cache(&cache_key).await {
-                tracks_from_cache.push(serde_json::from_value::<Track>(cached_track)?);
+                tracks.push(Some(serde_json::from_value::<Track>(cached_track)?));
             } else {
+                tracks.push(None);
                 tracks_to_fetch.push(id.clone());
             }
         }
 
-        // If all tracks were found in cache, return them directly
-        if tracks_to_fetch.is_empty() {
-            return Ok(TracksResponse { tracks: tracks_from_cache });
+        // Fetch missing tracks from Spotify API
+        let fetched: Vec<TracksResponse> = self.fetch_in_chunks(
+            &tracks_to_fetch,
+            MAX_TRACK_IDS,
+            |ids| format!("/tracks?ids={ids}{market_query}")
+        ).await?;
+        let mut fetched_tracks: HashMap<String, Track> = HashMap::new();
+        for track in fetched.into_iter().flat_map(|response| response.tracks) {
+            self.cache_track(&track, market).await?;
+            // A relinked track is returned under a different id than the requested one
+            let requested_id = track.linked_from
+                .as_ref()
+                .map_or(&track.id, |linked_from| &linked_from.id)
+                .clone();
+            fetched_tracks.insert(requested_id, track);
         }
 
-        // Fetch missing tracks from Spotify API
-        let ids_param = tracks_to_fetch.join(",");
-        let path = format!("/tracks?ids={ids_param}{market_query}");
-        let fetched_tracks: TracksResponse = self.get_spotify_data(&path).await?;
+        // Fill the gaps left by the cache in request order, dropping the tracks Spotify could not find
+        for (id, slot) in track_ids.iter().zip(tracks.iter_mut()) {
+            if slot.is_none() {
+                *slot = fetched_tracks.get(id).cloned();
+            }
+        }
+
+        Ok(TracksResponse { tracks: tracks.into_iter().flatten().collect() })
+    }
+
+    /// Caches `track` under the keys `get_several_tracks` looks it up by in `market`.
+    ///
+    /// A relinked track is returned under a different id than the requested one, so it is cached
+    /// under the requested id as well for later lookups in this market.
+    async fn cache_track(&self, track: &Track, market: Option<&str>) -> RustyResult<()> {
+        let value = serde_json::to_value(track)?;
+        if let Some(linked_from) = &track.linked_from {
+            self.update_cache(track_cache_key(&linked_from.id, market), value.clone()).await;
+        }
+        self.update_cache(track_cache_key(&track.id, market), value).await;
+        Ok(())
+    }
+
+    /// Fetches several tracks like [`get_several_tracks`](Self::get_several_tracks), keyed by
+    /// the requested Spotify IDs.
+    ///
+    /// Tracks relinked by Spotify are keyed by the requested ID, not the ID of the track they were
+    /// relinked to. IDs that Spotify could not resolve are simply absent from the map.
+    ///
+    /// # Arguments
+    /// * `track_ids` - A slice of Spotify IDs for the tracks.
+    /// * `market` - An optional market code, as for `get_several_tracks`.
+    ///
+    /// # Example
+    /// ```
+    /// # use rustyspoty::SpotifyClientCredentials;
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// let track_ids = vec!["track_id1".to_string(), "track_id2".to_string()];
+    /// let tracks = client.get_tracks_map(&track_ids, None).await?;
+    /// if let Some(track) = tracks.get("track_id1") {
+    ///     println!("Track name: {}", track.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_tracks_map(
+        &self,
+        track_ids: &[String],
+        market: Option<Market>
+    ) -> RustyResult<HashMap<String, Track>> {
+        let response = self.get_several_tracks(track_ids, market).await?;
+        Ok(
+            response.tracks
+                .into_iter()
+                .map(|track| {
+                    let requested_id = match &track.linked_from {
+                        Some(linked_from) => linked_from.id.clone(),
+                        None => track.id.clone(),
+                    };
+                    (requested_id, track)
+                })
+                .collect()
+        )
+    }
+
+    /// Fetches several tracks like [`get_tracks_map`](Self::get_tracks_map), also reporting the
+    /// requested IDs Spotify returned no track for.
+    ///
+    /// # Arguments
+    /// * `track_ids` - A slice of Spotify IDs for the tracks.
+    /// * `market` - An optional market code, as for `get_several_tracks`.
+    ///
+    /// # Example
+    /// ```
+    /// # use rustyspoty::SpotifyClientCredentials;
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// let track_ids = vec!["track_id1".to_string(), "not_a_track".to_string()];
+    /// let tracks = client.get_several_tracks_detailed(&track_ids, None).await?;
+    /// for id in &tracks.missing {
+    ///     println!("No track with id {id}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_several_tracks_detailed(
+        &self,
+        track_ids: &[String],
+        market: Option<Market>
+    ) -> RustyResult<BatchResult<Track>> {
+        let found = self.get_tracks_map(track_ids, market).await?;
+        Ok(BatchResult::new(track_ids, found))
+    }
+
+    /// Fetches the audio features of a track, such as its tempo, energy and danceability.
+    ///
+    /// # Arguments
+    /// * `track_id` - The Spotify ID of the track.
+    ///
+    /// # Returns
+    /// * `RustyResult<AudioFeatures>` - The track's audio features.
+    ///
+    /// # Caching
+    /// * The features are cached under `/audio-features/{track_id}`.
+    ///
+    /// # Example
+    /// ```
+    /// # use rustyspoty::SpotifyClientCredentials;
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// let features = client.get_audio_features("11dFghVXANMlKmJXsNCbNl").await?;
+    /// println!("Tempo: {} BPM, energy: {}", features.tempo, features.energy);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_audio_features(&self, track_id: &str) -> RustyResult<AudioFeatures> {
+        let path = format!("/audio-features/{track_id}");
+        self.get_spotify_data(&path).await
+    }
+
+    /// Fetches the low-level audio analysis of a track, such as its beats, sections and
+    /// segments, e.g. for beat-matching or visualizations.
+    ///
+    /// # Arguments
+    /// * `track_id` - The Spotify ID of the track.
+    ///
+    /// # Returns
+    /// * `RustyResult<AudioAnalysis>` - The track's audio analysis.
+    ///
+    /// # Caching
+    /// * The analysis is cached under `/audio-analysis/{track_id}`. Analyses of long tracks weigh
+    ///   several megabytes, so applications analysing many tracks once each may prefer
+    ///   [`get_audio_analysis_uncached`](Self::get_audio_analysis_uncached), which skips the
+    ///   copy kept by the cache.
+    ///
+    /// # Example
+    /// ```
+    /// # use rustyspoty::SpotifyClientCredentials;
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// let analysis = client.get_audio_analysis("11dFghVXANMlKmJXsNCbNl").await?;
+    /// println!("{} beats at {} BPM", analysis.beats.len(), analysis.track.tempo);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_audio_analysis(&self, track_id: &str) -> RustyResult<AudioAnalysis> {
+        let path = format!("/audio-analysis/{track_id}");
+        self.get_spotify_data(&path).await
+    }
+
+    /// Fetches the audio analysis of a track like
+    /// [`get_audio_analysis`](Self::get_audio_analysis), without reading or filling the cache.
+    ///
+    /// The response is deserialized straight into an `AudioAnalysis`, without the intermediate
+    /// copy the cache stores.
+    ///
+    /// # Arguments
+    /// * `track_id` - The Spotify ID of the track.
+    pub async fn get_audio_analysis_uncached(&self, track_id: &str) -> RustyResult<AudioAnalysis> {
+        let path = format!("/audio-analysis/{track_id}");
+        self.send_spotify_request::<AudioAnalysis, ()>(Method::GET, &path, None).await
+    }
+
+    /// Fetches the audio features of several tracks, using caching to optimize API usage.
+    ///
+    /// # Arguments
+    /// * `track_ids` - A slice of Spotify IDs for the tracks.
+    ///
+    /// # Returns
+    /// * `RustyResult<Vec<Option<AudioFeatures>>>`: The features of each requested track, in the
+    ///   order of `track_ids`. IDs Spotify has no features for are `None`, so indices always line
+    ///   up with `track_ids`.
+    ///
+    /// # Caching
+    /// * Features are cached per track under `/audio-features/{id}`, the key used by
+    ///   `get_audio_features`, so only the tracks missing from the cache are requested.
+    ///
+    /// # Batching
+    /// * The uncached IDs are split into requests of at most 100 IDs, Spotify's limit for this
+    ///   endpoint, and up to 4 of these requests are sent concurrently.
+    ///
+    /// # Example
+    /// ```
+    /// # use rustyspoty::SpotifyClientCredentials;
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// let track_ids = vec!["track_id1".to_string(), "track_id2".to_string()];
+    /// let features = client.get_several_audio_features(&track_ids).await?;
+    /// for (id, features) in track_ids.iter().zip(features) {
+    ///     match features {
+    ///         Some(features) => println!("{id}: {} BPM", features.tempo),
+    ///         None => println!("{id}: no audio features"),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_several_audio_features(
+        &self,
+        track_ids: &[String]
+    ) -> RustyResult<Vec<Option<AudioFeatures>>> {
+        if track_ids.is_empty() {
+            return Err(RustyError::invalid_input("Please provide at least 1 track ID."));
+        }
+
+        let mut features: Vec<Option<AudioFeatures>> = Vec::with_capacity(track_ids.len());
+        let mut ids_to_fetch = Vec::new();
+
+        // Check cache first
+        for id in track_ids {
+            if let Some(cached_features) = self.check_cache(&format!("/audio-features/{id}")).await {
+                features.push(Some(serde_json::from_value::<AudioFeatures>(cached_features)?));
+            } else {
+                features.push(None);
+                ids_to_fetch.push(id.clone());
+            }
+        }
+
+        // Fetch missing features from Spotify API
+        let fetched: Vec<AudioFeaturesResponse> = self.fetch_in_chunks(
+            &ids_to_fetch,
+            MAX_AUDIO_FEATURES_IDS,
+            |ids| format!("/audio-features?ids={ids}")
+        ).await?;
+        let mut fetched_features: HashMap<String, AudioFeatures> = HashMap::new();
+        let fetched = fetched.into_iter().flat_map(|response| response.audio_features.into_iter().flatten());
+        for track_features in fetched {
+            let value = serde_json::to_value(&track_features)?;
+            self.update_cache(format!("/audio-features/{}", track_features.id), value).await;
+            fetched_features.insert(track_features.id.clone(), track_features);
+        }
 
-        // Update cache with fetched tracks
-        for track in &fetched_tracks.tracks {
-            let cache_key = format!("/tracks/{}", track.id);
-            self.update_cache(cache_key, serde_json::to_value(track)?).await;
+        // Fill the gaps left by the cache in request order, keeping `None` for unknown tracks
+        for (id, slot) in track_ids.iter().zip(features.iter_mut()) {
+            if slot.is_none() {
+                *slot = fetched_features.get(id).cloned();
+            }
         }
 
-        // Combine cached tracks with fetched tracks before returning
-        let combined_tracks = [tracks_from_cache, fetched_tracks.tracks].concat();
-        Ok(TracksResponse { tracks: combined_tracks })
+        Ok(features)
     }
 
     /// Fetches track recommendations based on specified criteria from the Spotify API, utilizing caching to optimize performance.
@@ -738,20 +2393,23 @@ impl SpotifyClientCredentials {
     /// # Returns
     ///
     /// * `RustyResult<RecommendationsResponse>`: On success, it returns a `RecommendationsResponse` containing recommended tracks and their details.
-    /// On error, it returns a `RustyError` detailing what went wrong, such as invalid seed data or API request issues.
+    ///   Use [`RecommendationsResponse::coverage`] to check how much of the requested `limit` was filled; a warning is
+    ///   logged for every seed whose pool was filtered below the limit.
+    ///   On error, it returns a `RustyError` detailing what went wrong, such as invalid seed data or API request issues.
     ///
     /// # Caching:
     ///
     /// The function checks the cache for existing recommendations matching the request criteria before querying the Spotify API.
     /// If cached data is found and it's not expired, the function returns the cached data instead of making a new API request.
-    /// After fetching recommendations from the API, the function updates the cache with the new data of tracks for future use.
+    /// After fetching recommendations from the API, the function caches the recommended tracks per market, like
+    /// `get_several_tracks`, including relinked tracks under the ID they were relinked from.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use rustyspoty::{SpotifyClientCredentials, models::recommendations::RecommendationsRequest};
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
-    /// # let mut spotify_client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// # let spotify_client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
     /// let mut request = RecommendationsRequest::new();
     /// request.seed_genres = Some(vec!["pop".to_string()]);
     /// request.limit = Some(10);
@@ -764,16 +2422,30 @@ impl SpotifyClientCredentials {
     /// # }
     /// ```
     pub async fn get_recommendations(
-        &mut self,
+        &self,
         request: &RecommendationsRequest
+    ) -> RustyResult<RecommendationsResponse> {
+        let market = self.resolve_market(request.market)?;
+        let response = self.fetch_recommendations(request, market.as_deref()).await?;
+
+        // Update cache with fetched tracks, under the keys `get_several_tracks` looks them up by
+        for track in &response.tracks {
+            self.cache_track(track, market.as_deref()).await?;
+        }
+
+        Ok(response)
+    }
+
+    /// Fetches track recommendations in the resolved `market`, without caching the recommended tracks.
+    async fn fetch_recommendations(
+        &self,
+        request: &RecommendationsRequest,
+        market: Option<&str>
     ) -> RustyResult<RecommendationsResponse> {
         // Validation logic for seeds
-        let total_seeds: usize =
-            request.seed_artists.as_ref().map_or(0, Vec::len) +
-            request.seed_genres.as_ref().map_or(0, Vec::len) +
-            request.seed_tracks.as_ref().map_or(0, Vec::len);
+        let total_seeds: usize = request.seed_count();
 
-        if total_seeds == 0 || total_seeds > 5 {
+        if total_seeds == 0 || total_seeds > MAX_SEEDS {
             let err_msg = if total_seeds == 0 {
                 "At least one seed (artist, genre, or track) is required."
             } else {
@@ -782,18 +2454,85 @@ impl SpotifyClientCredentials {
             return Err(RustyError::invalid_input(err_msg));
         }
 
-        // Serialize the request object to a JSON value
-        let request_json: Value = request.to_json()?;
+        // Serialize the request object to a JSON value, with seeds given as URIs or URLs reduced to IDs
+        let mut request_json: Value = request.to_normalized_json()?;
+        if let Some(market) = market {
+            request_json["market"] = market.into();
+        }
 
         // Convert the JSON value to a query string and append it to the endpoint path
         let query_params: String = self.to_query_string(&request_json);
         let path: String = format!("/recommendations?{}", query_params);
 
-        let response = self.get_spotify_data::<RecommendationsResponse>(&path).await?;
-        // Update cache with fetched tracks
-        for track in &response.tracks {
-            let cache_key = format!("/tracks/{}", track.id);
-            self.update_cache(cache_key, serde_json::to_value(track)?).await;
+        let mut response = self.get_spotify_data::<RecommendationsResponse>(&path).await?;
+        response.requested_limit = Some(request.limit.unwrap_or(DEFAULT_RECOMMENDATIONS_LIMIT));
+        for seed in response.constrained_seeds() {
+            log::warn!(
+                "recommendation seed {} {} has {} tracks left after filtering, below the requested limit of {}; the tunable attributes may be too restrictive",
+                seed.r#type,
+                seed.id,
+                seed.afterFilteringSize,
+                response.requested_limit.unwrap_or(DEFAULT_RECOMMENDATIONS_LIMIT)
+            );
+        }
+
+        Ok(response)
+    }
+
+    /// Fetches track recommendations like [`get_recommendations`](Self::get_recommendations), then
+    /// replaces the recommended tracks with complete ones fetched from the tracks endpoint.
+    ///
+    /// Spotify may leave fields out of the tracks returned by the recommendations endpoint, most
+    /// notably `available_markets` and `popularity`, and omits `available_markets` altogether
+    /// when a `market` is requested. Hydrating costs one extra request per 50 uncached recommended
+    /// tracks, so only use this method when the complete tracks are needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `request`: The seeds and tunable attributes, as for `get_recommendations`. Its `market`
+    ///   is also used to fetch the complete tracks.
+    ///
+    /// # Returns
+    ///
+    /// * `RustyResult<RecommendationsResponse>`: The recommendations, with complete tracks in the
+    ///   order they were recommended.
+    ///
+    /// # Caching
+    ///
+    /// The incomplete recommended tracks are not cached. The complete tracks are looked up through
+    /// [`get_several_tracks`](Self::get_several_tracks), so tracks already cached in the request's
+    /// market are served from the cache, and the others are fetched and cached per market. Tracks
+    /// cached by an earlier `get_recommendations` call are as incomplete as that call returned them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use rustyspoty::{SpotifyClientCredentials, models::recommendations::RecommendationsRequest};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let spotify_client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// let mut request = RecommendationsRequest::new();
+    /// request.seed_genres = Some(vec!["pop".to_string()]);
+    ///
+    /// let recommendations = spotify_client.get_recommendations_hydrated(&request).await?;
+    /// for track in recommendations.tracks {
+    ///     println!("{} is available in {:?}", track.name, track.availability());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_recommendations_hydrated(
+        &self,
+        request: &RecommendationsRequest
+    ) -> RustyResult<RecommendationsResponse> {
+        let market = self.resolve_market(request.market)?;
+        let mut response = self.fetch_recommendations(request, market.as_deref()).await?;
+
+        if !response.tracks.is_empty() {
+            let track_ids: Vec<String> = response.tracks
+                .iter()
+                .map(|track| track.id.clone())
+                .collect();
+            response.tracks = self.get_several_tracks(&track_ids, request.market).await?.tracks;
         }
 
         Ok(response)
@@ -811,69 +2550,404 @@ impl SpotifyClientCredentials {
     /// ```
     /// # use rustyspoty::SpotifyClientCredentials;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
-    /// # let mut client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// # let client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
     /// let playlist_id = "37i9dQZF1DXcBWIGoYBM5M";
     /// let playlist_info = client.get_playlist(playlist_id).await?;
     /// println!("Playlist Name: {}", playlist_info.name);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn get_playlist(&mut self, playlist_id: &str) -> RustyResult<Playlist> {
+    pub async fn get_playlist(&self, playlist_id: &str) -> RustyResult<Playlist> {
         let path = format!("/playlists/{playlist_id}");
         self.get_spotify_data(&path).await
     }
 
-    /// Converts a `serde_json::Value` into a URL-encoded query string.
+    /// Fetches a playlist unless it did not change since `validators` were obtained.
     ///
-    /// This utility function is designed to serialize API parameters stored in a `serde_json::Value`
-    /// into a string format suitable for use as HTTP query parameters. It supports arrays, strings,
-    /// numbers, and booleans, accurately representing these types in the query string. This ensures
-    /// that API requests can include a diverse range of parameters.
+    /// Spotify returns an `ETag` with playlists, so storing it with the playlist lets an
+    /// application check for changes without downloading an unchanged playlist again. The request
+    /// bypasses the client's cache.
     ///
     /// # Arguments
-    /// * `params` - A `serde_json::Value` representing the JSON object with the API query parameters.
+    /// * `playlist_id` - The Spotify ID of the playlist.
+    /// * `validators` - The validators returned with the stored playlist.
     ///
-    /// # Returns
-    /// * `String` - A URL-encoded string of the query parameters.
+    /// # Example
+    /// ```
+    /// # use rustyspoty::{ Conditional, SpotifyClientCredentials, Validators };
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// let validators = Validators { etag: Some("\"MC,0,0,0,0\"".to_string()), last_modified: None };
+    /// if let Conditional::Modified(playlist, validators) = client.get_playlist_if_modified("3cEYpjA9oz9GiPac4AsH4n", &validators).await? {
+    ///     println!("{} changed, store ETag {:?}", playlist.name, validators.etag);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_playlist_if_modified(
+        &self,
+        playlist_id: &str,
+        validators: &Validators
+    ) -> RustyResult<Conditional<Playlist>> {
+        let path = format!("/playlists/{playlist_id}");
+        self.get_spotify_data_if_modified(&path, validators).await
+    }
+
+    /// Fetches a podcast episode by its Spotify ID.
     ///
-    /// # Examples
+    /// # Arguments
+    /// * `episode_id` - The Spotify ID of the episode.
+    /// * `market` - An optional ISO 3166-1 alpha-2 country code. Spotify considers many episodes
+    ///   unavailable without a market. Falls back to the client's default market when `None`.
     ///
-    /// Converting a JSON object with various types of parameters into a query string:
+    /// # Returns
+    /// * `RustyResult<Episode>` - The episode.
     ///
+    /// # Example
     /// ```
-    /// # use rustyspoty::SpotifyClientCredentials;
-    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
-    /// # let mut client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
-    /// let params = serde_json::json!({
-    ///     "limit": 10,
-    ///     "seed_genres": ["acoustic", "afrobeat"],
-    ///     "market": "US",
-    ///     "min_energy": 0.4
-    /// });
-    ///
-    /// let query_string = client.to_query_string(&params);
-    /// assert_eq!(query_string, "limit=10&seed_genres=acoustic,afrobeat&market=US&min_energy=0.4");
+    /// # use rustyspoty::{ models::market::Market, SpotifyClientCredentials };
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// let episode = client.get_episode("512ojhOuo1ktJprKbVcKyQ", Some(Market::US)).await?;
+    /// println!("Episode: {} ({} ms)", episode.name, episode.duration_ms);
     /// # Ok(())
     /// # }
     /// ```
+    pub async fn get_episode(&self, episode_id: &str, market: Option<Market>) -> RustyResult<Episode> {
+        let market = self.resolve_market(market)?;
+        let market_query = market.map_or(String::new(), |m| format!("?market={}", m));
+        let path = format!("/episodes/{episode_id}{market_query}");
+        self.get_spotify_data(&path).await
+    }
+
+    /// Fetches several podcast episodes by their Spotify IDs.
     ///
-    /// Note: This function ignores null values and objects, focusing on directly serializable types.
-    pub fn to_query_string(&self, params: &Value) -> String {
-        params.as_object().map_or_else(String::new, |obj| {
-            obj.iter()
-                .filter_map(|(key, value)| {
-                    match value {
+    /// # Arguments
+    /// * `episode_ids` - A slice of Spotify IDs for the episodes. Maximum of 50 IDs allowed.
+    /// * `market` - An optional ISO 3166-1 alpha-2 country code, as for `get_episode`.
+    ///
+    /// # Returns
+    /// * `RustyResult<Episodes>` - The episodes Spotify could find, in request order.
+    ///
+    /// # Errors
+    /// * Returns an error if no episode IDs are provided or if the number of IDs exceeds the limit of 50.
+    ///
+    /// # Example
+    /// ```
+    /// # use rustyspoty::{ models::market::Market, SpotifyClientCredentials };
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// let episode_ids = vec!["episode_id1".to_string(), "episode_id2".to_string()];
+    /// let episodes = client.get_several_episodes(&episode_ids, Some(Market::US)).await?;
+    /// for episode in episodes.episodes {
+    ///     println!("Episode: {}", episode.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_several_episodes(
+        &self,
+        episode_ids: &[String],
+        market: Option<Market>
+    ) -> RustyResult<Episodes> {
+        if episode_ids.is_empty() {
+            return Err(RustyError::invalid_input("Please provide at least 1 episode ID."));
+        }
+        if episode_ids.len() > MAX_EPISODE_IDS {
+            return Err(RustyError::invalid_input("Maximum of 50 IDs."));
+        }
+
+        let market = self.resolve_market(market)?;
+        let market_query = market.map_or(String::new(), |m| format!("&market={}", m));
+        let path = format!("/episodes?ids={}{market_query}", episode_ids.join(","));
+        self.get_spotify_data(&path).await
+    }
+
+    /// Fetches a podcast show by its Spotify ID.
+    ///
+    /// # Arguments
+    /// * `show_id` - The Spotify ID of the show.
+    /// * `market` - An optional ISO 3166-1 alpha-2 country code. Falls back to the client's
+    ///   default market when `None`.
+    ///
+    /// # Returns
+    /// * `RustyResult<Show>` - The show, without its episodes; see `get_show_episodes`.
+    ///
+    /// # Example
+    /// ```
+    /// # use rustyspoty::{ models::market::Market, SpotifyClientCredentials };
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// let show = client.get_show("38bS44xjbVVZ3No3ByF1dJ", Some(Market::US)).await?;
+    /// println!("{} by {}, {} episodes", show.name, show.publisher, show.total_episodes);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_show(&self, show_id: &str, market: Option<Market>) -> RustyResult<Show> {
+        let market = self.resolve_market(market)?;
+        let market_query = market.map_or(String::new(), |m| format!("?market={}", m));
+        let path = format!("/shows/{show_id}{market_query}");
+        self.get_spotify_data(&path).await
+    }
+
+    /// Fetches a page of the episodes of a podcast show.
+    ///
+    /// # Arguments
+    /// * `show_id` - The Spotify ID of the show.
+    /// * `market` - An optional ISO 3166-1 alpha-2 country code. Falls back to the client's
+    ///   default market when `None`.
+    /// * `limit`: The maximum number of episodes to return, clamped to 1-50. Defaults to 20.
+    /// * `offset`: The index of the first episode to return, at least 0. Defaults to 0.
+    ///
+    /// # Returns
+    /// * `RustyResult<Page<Episode>>` - A page of the show's episodes.
+    ///
+    /// # Example
+    /// ```
+    /// # use rustyspoty::{ models::market::Market, SpotifyClientCredentials };
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// let episodes = client.get_show_episodes("38bS44xjbVVZ3No3ByF1dJ", Some(Market::US), Some(10), None).await?;
+    /// for episode in episodes.items {
+    ///     println!("Episode: {}", episode.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_show_episodes(
+        &self,
+        show_id: &str,
+        market: Option<Market>,
+        limit: Option<i32>,
+        offset: Option<i32>
+    ) -> RustyResult<Page<Episode>> {
+        let limit = limit.unwrap_or(20).clamp(1, 50); // Ensures limit is within 1-50
+        let offset = offset.unwrap_or(0).max(0); // Ensures offset is non-negative
+
+        let mut path = format!("/shows/{show_id}/episodes?limit={limit}&offset={offset}");
+        if let Some(market) = self.resolve_market(market)? {
+            path.push_str(&format!("&market={market}"));
+        }
+        self.get_spotify_data(&path).await
+    }
+
+    /// Fetches the public profile of a Spotify user.
+    ///
+    /// # Arguments
+    /// * `user_id` - The Spotify user ID of the user, e.g. `"smedjan"`.
+    ///
+    /// # Returns
+    /// * `RustyResult<User>` - The user's public profile, including their followers and images.
+    ///
+    /// # Example
+    /// ```
+    /// # use rustyspoty::SpotifyClientCredentials;
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// let user = client.get_user("smedjan").await?;
+    /// println!("{} has {:?} followers", user.id, user.followers.map(|f| f.total));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_user(&self, user_id: &str) -> RustyResult<User> {
+        let path = format!("/users/{user_id}");
+        self.get_spotify_data(&path).await
+    }
+
+    /// Searches the Spotify catalog for items matching a keyword query.
+    ///
+    /// # Arguments
+    /// * `query` - The search query, e.g. `"daft punk"` or `"album:discovery artist:daft punk"`.
+    ///   It is URL-encoded, so spaces and special characters can be passed as is.
+    /// * `types` - The kinds of items to search for, at least one. Each gets its own page of
+    ///   results in `SearchResults`.
+    /// * `market` - An optional ISO 3166-1 alpha-2 country code, to only return items available in
+    ///   that market. Falls back to the client's default market when `None`.
+    /// * `limit` - The maximum number of items per type, clamped to 1-50. Defaults to 20.
+    /// * `offset` - The index of the first item to return. Defaults to 0.
+    ///
+    /// Spotify serves at most the first 1000 results of a search: `offset + limit` must not
+    /// exceed 1000.
+    ///
+    /// # Returns
+    /// * `RustyResult<SearchResults>`: The page of results of each requested type.
+    ///
+    /// # Errors
+    /// * Returns `RustyError::Io` with `InvalidInput`, without sending a request, if `types` is
+    ///   empty or `offset + limit` exceeds 1000.
+    ///
+    /// # Example
+    /// ```
+    /// # use rustyspoty::{ models::search::SearchType, SpotifyClientCredentials };
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// let results = client.search("daft punk", &[SearchType::Artist], None, Some(5), None).await?;
+    /// for artist in results.artists.map(|page| page.items).unwrap_or_default() {
+    ///     println!("Artist: {}", artist.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn search(
+        &self,
+        query: &str,
+        types: &[SearchType],
+        market: Option<Market>,
+        limit: Option<u32>,
+        offset: Option<u32>
+    ) -> RustyResult<SearchResults> {
+        if types.is_empty() {
+            return Err(RustyError::invalid_input("search requires at least one search type"));
+        }
+        let limit = limit.unwrap_or(20).clamp(1, MAX_PAGE_LIMIT as u32);
+        let offset = offset.unwrap_or(0);
+        validate_offset(offset as usize, limit as usize)?;
+
+        let types: Vec<&str> = types
+            .iter()
+            .map(SearchType::as_str)
+            .collect();
+        let params =
+            serde_json::json!({
+            "q": query,
+            "type": types,
+            "market": self.resolve_market(market)?,
+            "limit": limit,
+            "offset": offset,
+        });
+        let path = format!("/search?{}", self.to_query_string(&params));
+        self.get_spotify_data(&path).await
+    }
+
+    /// Searches the Spotify catalog for items of a single type, following the pages of results
+    /// until `max_results` items were collected or the results are exhausted.
+    ///
+    /// Spotify serves at most the first 1000 results of a search, so `max_results` must not
+    /// exceed 1000: fewer items than `max_results` then always means the results are exhausted.
+    ///
+    /// # Arguments
+    /// * `query` - The search query, see [`SpotifyClientCredentials::search`].
+    /// * `search_type` - The kind of items to search for. Types paginate independently, so a
+    ///   single one is searched per call.
+    /// * `market` - An optional ISO 3166-1 alpha-2 country code, to only return items available in
+    ///   that market. Falls back to the client's default market when `None`.
+    /// * `max_results` - The most items to return, at most 1000.
+    ///
+    /// # Returns
+    /// * `RustyResult<Vec<SearchItem>>`: The matching items, in the order Spotify ranks them.
+    ///
+    /// # Errors
+    /// * Returns `RustyError::Io` with `InvalidInput`, without sending a request, if
+    ///   `max_results` exceeds the 1000-result cap.
+    ///
+    /// # Caching
+    /// * Each page is cached separately, like the result of `search`.
+    ///
+    /// # Example
+    /// ```
+    /// # use rustyspoty::{ models::search::SearchType, SpotifyClientCredentials };
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// let playlists = client.search_all("lofi", SearchType::Playlist, None, 200).await?;
+    /// println!("{} playlists", playlists.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn search_all(
+        &self,
+        query: &str,
+        search_type: SearchType,
+        market: Option<Market>,
+        max_results: usize
+    ) -> RustyResult<Vec<SearchItem>> {
+        if max_results > MAX_OFFSET_PLUS_LIMIT {
+            return Err(
+                RustyError::invalid_input(
+                    &format!(
+                        "max_results must not exceed {MAX_OFFSET_PLUS_LIMIT}, the most results Spotify serves for a search, got {max_results}"
+                    )
+                )
+            );
+        }
+
+        let mut items = Vec::new();
+        let mut offset = 0;
+
+        while items.len() < max_results {
+            let limit = MAX_PAGE_LIMIT.min(max_results - items.len());
+            let results = self.search(
+                query,
+                &[search_type],
+                market,
+                Some(limit as u32),
+                Some(offset as u32)
+            ).await?;
+            let Some(page) = results.into_page(search_type) else {
+                break;
+            };
+            let received = page.items.len();
+            items.extend(page.items);
+            if page.next.is_none() || received == 0 {
+                break;
+            }
+            offset += received;
+        }
+
+        items.truncate(max_results);
+        Ok(items)
+    }
+
+    /// Converts a `serde_json::Value` into a URL-encoded query string.
+    ///
+    /// This utility function is designed to serialize API parameters stored in a `serde_json::Value`
+    /// into a string format suitable for use as HTTP query parameters. It supports arrays, strings,
+    /// numbers, and booleans, accurately representing these types in the query string. This ensures
+    /// that API requests can include a diverse range of parameters.
+    ///
+    /// # Arguments
+    /// * `params` - A `serde_json::Value` representing the JSON object with the API query parameters.
+    ///
+    /// # Returns
+    /// * `String` - A URL-encoded string of the query parameters.
+    ///
+    /// # Examples
+    ///
+    /// Converting a JSON object with various types of parameters into a query string:
+    ///
+    /// ```
+    /// # use rustyspoty::SpotifyClientCredentials;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = SpotifyClientCredentials::new("client_id".to_string(), "client_secret".to_string());
+    /// let params = serde_json::json!({
+    ///     "limit": 10,
+    ///     "seed_genres": ["acoustic", "afrobeat"],
+    ///     "market": "US",
+    ///     "min_energy": 0.4
+    /// });
+    ///
+    /// let query_string = client.to_query_string(&params);
+    /// assert_eq!(query_string, "limit=10&seed_genres=acoustic,afrobeat&market=US&min_energy=0.4");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Note: This function ignores null values and objects, focusing on directly serializable types.
+    pub fn to_query_string(&self, params: &Value) -> String {
+        params.as_object().map_or_else(String::new, |obj| {
+            obj.iter()
+                .filter_map(|(key, value)| {
+                    match value {
                         Value::Array(vals) => {
-                            // Handle arrays: join their string representations with commas
+                            // Handle arrays: join their encoded string representations with commas
                             let vals_str: Vec<String> = vals
                                 .iter()
-                                .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                                .filter_map(|v| v.as_str().map(encode_query_value))
                                 .collect();
                             Some(format!("{}={}", key, vals_str.join(",")))
                         }
                         Value::String(str_val) => {
                             // Handle strings directly
-                            Some(format!("{}={}", key, str_val))
+                            Some(format!("{}={}", key, encode_query_value(str_val)))
                         }
                         // Handle numerical and boolean values by converting them to strings
                         Value::Number(num_val) => Some(format!("{}={}", key, num_val)),
@@ -892,21 +2966,1863 @@ impl SpotifyClientCredentials {
 mod tests {
     use super::*;
     use std::env;
+    use wiremock::{
+        matchers::{ body_json, header, method, path, query_param },
+        Mock,
+        MockServer,
+        ResponseTemplate,
+    };
     // use serde_json::json;
 
     fn setup() -> SpotifyClientCredentials {
         dotenv::dotenv().ok();
-        // Setup
-        let client_id: String = env::var("SPOTIFY_CLIENT_ID").expect("Expected a client id");
-        let client_secret: String = env
-            ::var("SPOTIFY_CLIENT_SECRET")
-            .expect("Expected a client secret");
-        SpotifyClientCredentials::new(client_id, client_secret)
+        SpotifyClientCredentials::from_env().unwrap()
+    }
+
+    /// Creates a client sending its API and token requests to `server`, which issues `test_token`.
+    async fn mock_client(server: &MockServer) -> SpotifyClientCredentials {
+        mount_token(server).await;
+
+        let mut client = SpotifyClientCredentials::new(
+            "client_id".to_string(),
+            "client_secret".to_string()
+        );
+        client.use_mock_server(&server.uri());
+        client
+    }
+
+    /// Serves access tokens from the mock server's token endpoint.
+    async fn mount_token(server: &MockServer) {
+        Mock::given(method("POST"))
+            .and(path("/api/token"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({
+                        "access_token": "test_token",
+                        "token_type": "Bearer",
+                        "expires_in": 3600
+                    })
+                )
+            )
+            .mount(server).await;
+    }
+
+    fn album_json(album_id: &str) -> Value {
+        serde_json::json!({
+            "album_type": "album",
+            "total_tracks": 1,
+            "external_urls": { "spotify": format!("https://open.spotify.com/album/{album_id}") },
+            "href": format!("https://api.spotify.com/v1/albums/{album_id}"),
+            "id": album_id,
+            "images": [],
+            "name": "Album Name",
+            "release_date": "2024-01-01",
+            "release_date_precision": "day",
+            "type": "album",
+            "uri": format!("spotify:album:{album_id}"),
+            "artists": [],
+            "tracks": {
+                "href": format!("https://api.spotify.com/v1/albums/{album_id}/tracks"),
+                "items": [],
+                "limit": 50,
+                "next": null,
+                "offset": 0,
+                "previous": null,
+                "total": 0
+            },
+            "copyrights": [],
+            "genres": [],
+            "popularity": 50,
+            "label": null
+        })
+    }
+
+    fn artist_json(artist_id: &str) -> Value {
+        serde_json::json!({
+            "id": artist_id,
+            "name": "Artist Name",
+            "images": [],
+            "external_urls": { "spotify": format!("https://open.spotify.com/artist/{artist_id}") },
+            "followers": { "total": 10 },
+            "genres": ["pop"],
+            "type": "artist",
+            "uri": format!("spotify:artist:{artist_id}"),
+            "popularity": 50
+        })
+    }
+
+    fn simplified_album_json(album_id: &str) -> Value {
+        serde_json::json!({
+            "album_type": "album",
+            "total_tracks": 1,
+            "available_markets": [],
+            "external_urls": { "spotify": format!("https://open.spotify.com/album/{album_id}") },
+            "href": format!("https://api.spotify.com/v1/albums/{album_id}"),
+            "id": album_id,
+            "images": [],
+            "name": "Album Name",
+            "release_date": "2024-01-01",
+            "release_date_precision": "day",
+            "type": "album",
+            "uri": format!("spotify:album:{album_id}"),
+            "artists": []
+        })
+    }
+
+    fn track_json(track_id: &str) -> Value {
+        serde_json::json!({
+            "album": simplified_album_json("album1"),
+            "id": track_id,
+            "name": "Track Name",
+            "artists": [],
+            "duration_ms": 180000,
+            "preview_url": null,
+            "external_urls": { "spotify": format!("https://open.spotify.com/track/{track_id}") }
+        })
+    }
+
+    fn simplified_track_json(track_id: &str) -> Value {
+        serde_json::json!({
+            "artists": [],
+            "disc_number": 1,
+            "duration_ms": 180000,
+            "explicit": false,
+            "external_urls": { "spotify": format!("https://open.spotify.com/track/{track_id}") },
+            "href": format!("https://api.spotify.com/v1/tracks/{track_id}"),
+            "id": track_id,
+            "name": "Track Name",
+            "preview_url": null,
+            "track_number": 1
+        })
+    }
+
+    fn audio_features_json(track_id: &str) -> Value {
+        serde_json::json!({
+            "acousticness": 0.00242,
+            "analysis_url": format!("https://api.spotify.com/v1/audio-analysis/{track_id}"),
+            "danceability": 0.585,
+            "duration_ms": 237040,
+            "energy": 0.842,
+            "id": track_id,
+            "instrumentalness": 0.00686,
+            "key": 9,
+            "liveness": 0.0866,
+            "loudness": -5.883,
+            "mode": 0,
+            "speechiness": 0.0556,
+            "tempo": 118.211,
+            "time_signature": 4,
+            "track_href": format!("https://api.spotify.com/v1/tracks/{track_id}"),
+            "type": "audio_features",
+            "uri": format!("spotify:track:{track_id}"),
+            "valence": 0.428
+        })
+    }
+
+    fn episode_json(episode_id: &str) -> Value {
+        serde_json::json!({
+            "audio_preview_url": null,
+            "description": "A description.",
+            "duration_ms": 1686230,
+            "explicit": false,
+            "external_urls": { "spotify": format!("https://open.spotify.com/episode/{episode_id}") },
+            "href": format!("https://api.spotify.com/v1/episodes/{episode_id}"),
+            "html_description": "<p>A description.</p>",
+            "id": episode_id,
+            "images": [],
+            "is_externally_hosted": false,
+            "languages": ["en"],
+            "name": "Episode Name",
+            "release_date": "2024-01-01",
+            "release_date_precision": "day",
+            "type": "episode",
+            "uri": format!("spotify:episode:{episode_id}")
+        })
+    }
+
+    /// Responds to `/tracks?ids=..` with the requested tracks, after a fixed delay.
+    struct DelayedTracks(Duration);
+
+    impl wiremock::Respond for DelayedTracks {
+        fn respond(&self, request: &wiremock::Request) -> ResponseTemplate {
+            let ids = request.url
+                .query_pairs()
+                .find(|(key, _)| key == "ids")
+                .map(|(_, ids)| ids.into_owned())
+                .unwrap_or_default();
+            let tracks: Vec<Value> = ids.split(',').map(track_json).collect();
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({ "tracks": tracks }))
+                .set_delay(self.0)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_several_artists_populates_cache() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/artists"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({ "artists": [artist_json("artist1"), artist_json("artist2")] })
+                )
+            )
+            .mount(&server).await;
+
+        let ids = ["artist1".to_string(), "artist2".to_string()];
+        client.get_several_artists(&ids).await.unwrap();
+
+        assert!(client.cache_contains("/artists/artist1").await);
+        assert!(client.cache_contains("/artists/artist2").await);
+        assert!(!client.cache_contains("/artists/artist3").await);
+        // One entry per artist, plus the batch response itself
+        assert_eq!(client.cache_len().await, 3);
+    }
+
+    #[tokio::test]
+    async fn test_with_http_client_sends_token_and_api_requests_through_it() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/token"))
+            .and(header("x-app", "rustyspoty-test"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({
+                        "access_token": "test_token",
+                        "token_type": "Bearer",
+                        "expires_in": 3600
+                    })
+                )
+            )
+            .expect(1)
+            .mount(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/albums/album1"))
+            .and(header("x-app", "rustyspoty-test"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(album_json("album1")))
+            .expect(1)
+            .mount(&server).await;
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("x-app", reqwest::header::HeaderValue::from_static("rustyspoty-test"));
+        let http_client = ReqwestClient::builder().default_headers(headers).build().unwrap();
+        let mut client = SpotifyClientCredentials::with_http_client(
+            "client_id".to_string(),
+            "client_secret".to_string(),
+            http_client
+        );
+        client.use_mock_server(&server.uri());
+
+        let album = client.get_album("album1").await.unwrap();
+        assert_eq!(album.id, "album1");
+    }
+
+    #[tokio::test]
+    async fn test_getters_accept_uris_and_urls() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/albums/album1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(album_json("album1")))
+            .expect(1)
+            .mount(&server).await;
+
+        let album = client.get_album("spotify:album:album1").await.unwrap();
+        assert_eq!(album.id, "album1");
+        // Served from the cache entry of the bare ID
+        let album = client.get_album("https://open.spotify.com/album/album1?si=abc").await.unwrap();
+        assert_eq!(album.id, "album1");
+
+        let result = client.get_track("spotify:album:album1").await;
+        assert!(matches!(result, Err(RustyError::Io(e)) if e.kind() == std::io::ErrorKind::InvalidInput));
+    }
+
+    #[tokio::test]
+    async fn test_get_artist_albums_filters_groups() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/artists/artist1/albums"))
+            .and(query_param("include_groups", "album,compilation"))
+            .and(query_param("limit", "50"))
+            .and(query_param("offset", "0"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({
+                        "href": "",
+                        "items": [],
+                        "limit": 50,
+                        "next": null,
+                        "offset": 0,
+                        "previous": null,
+                        "total": 0
+                    })
+                )
+            )
+            .expect(1)
+            .mount(&server).await;
+
+        let groups = [AlbumGroup::Album, AlbumGroup::Compilation];
+        let page = client.get_artist_albums("artist1", Some(&groups), None, Some(80), None).await;
+        assert!(page.unwrap().items.is_empty());
+        assert!(
+            client.cache.contains_key(
+                "/artists/artist1/albums?include_groups=album,compilation&limit=50&offset=0"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_album_cached_reports_cache_hit() {
+        let client = SpotifyClientCredentials::new(
+            "client_id".to_string(),
+            "client_secret".to_string()
+        );
+        let before = Instant::now();
+        client.update_cache("/albums/album1".to_string(), album_json("album1")).await;
+
+        let album = client.get_album_cached("album1").await.unwrap();
+        assert!(album.from_cache);
+        assert!(album.fetched_at >= before);
+        assert_eq!(album.value.id, "album1");
+    }
+
+    #[tokio::test]
+    async fn test_get_several_tracks_fetches_chunks_concurrently() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        let delay = Duration::from_millis(300);
+        Mock::given(method("GET"))
+            .and(path("/v1/tracks"))
+            .respond_with(DelayedTracks(delay))
+            .expect(3)
+            .mount(&server).await;
+
+        // 120 ids need 3 requests of at most 50 ids
+        let ids: Vec<String> = (0..120).map(|i| format!("track{i}")).collect();
+        let start = Instant::now();
+        let response = client.get_several_tracks(&ids, None).await.unwrap();
+        let elapsed = start.elapsed();
+
+        // Sequential chunks would take at least 3 round trips
+        assert!(elapsed < delay * 2, "batch took {elapsed:?}");
+        let fetched: Vec<&str> = response.tracks
+            .iter()
+            .map(|track| track.id.as_str())
+            .collect();
+        assert_eq!(fetched, ids);
+        assert_eq!(client.cache_len().await, 120);
+    }
+
+    #[tokio::test]
+    async fn test_get_recommendations_hydrated_replaces_tracks() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        let mut partial_track = track_json("track1");
+        partial_track["name"] = "Partial Track".into();
+        Mock::given(method("GET"))
+            .and(path("/v1/recommendations"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({ "seeds": [], "tracks": [partial_track] })
+                )
+            )
+            .mount(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/tracks"))
+            .respond_with(DelayedTracks(Duration::ZERO))
+            .expect(1)
+            .mount(&server).await;
+
+        let mut request = RecommendationsRequest::new();
+        request.seed_genres = Some(vec!["pop".to_string()]);
+        let response = client.get_recommendations_hydrated(&request).await.unwrap();
+
+        assert_eq!(response.tracks.len(), 1);
+        assert_eq!(response.tracks[0].name, "Track Name");
+        let cached = client.check_cache("/tracks/track1").await.unwrap();
+        assert_eq!(cached["name"], "Track Name");
+    }
+
+    #[tokio::test]
+    async fn test_get_recommendations_hydrated_serves_cached_tracks() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        let mut partial_track = track_json("track1");
+        partial_track["name"] = "Partial Track".into();
+        Mock::given(method("GET"))
+            .and(path("/v1/recommendations"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({ "seeds": [], "tracks": [partial_track] })
+                )
+            )
+            .mount(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/tracks"))
+            .respond_with(DelayedTracks(Duration::ZERO))
+            .expect(1)
+            .mount(&server).await;
+
+        let ids = ["track1".to_string()];
+        client.get_several_tracks(&ids, None).await.unwrap();
+        let mut request = RecommendationsRequest::new();
+        request.seed_genres = Some(vec!["pop".to_string()]);
+        let response = client.get_recommendations_hydrated(&request).await.unwrap();
+
+        // The complete track cached above is reused instead of being fetched again
+        assert_eq!(response.tracks[0].name, "Track Name");
+    }
+
+    #[tokio::test]
+    async fn test_relinked_recommendations_are_cached_per_market() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        let mut relinked_track = track_json("track2");
+        relinked_track["linked_from"] = serde_json::json!({
+            "external_urls": { "spotify": "https://open.spotify.com/track/track1" },
+            "href": "https://api.spotify.com/v1/tracks/track1",
+            "id": "track1",
+            "type": "track",
+            "uri": "spotify:track:track1"
+        });
+        Mock::given(method("GET"))
+            .and(path("/v1/recommendations"))
+            .and(query_param("market", "DE"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({ "seeds": [], "tracks": [relinked_track] })
+                )
+            )
+            .expect(1)
+            .mount(&server).await;
+
+        let request = RecommendationsRequest::builder()
+            .seed_genres(["pop"])
+            .market(Market::DE)
+            .build()
+            .unwrap();
+        client.get_recommendations(&request).await.unwrap();
+
+        assert!(client.cache_contains("/tracks/track2?market=DE").await);
+        assert!(client.cache_contains("/tracks/track1?market=DE").await);
+        assert!(!client.cache_contains("/tracks/track1").await);
+        assert!(!client.cache_contains("/tracks/track2").await);
+
+        // The requested id is now served from the cache
+        let ids = ["track1".to_string()];
+        let tracks = client.get_several_tracks(&ids, Some(Market::DE)).await.unwrap();
+        assert_eq!(tracks.tracks[0].id, "track2");
+    }
+
+    #[tokio::test]
+    async fn test_recommendations_market_is_resolved() {
+        let server = MockServer::start().await;
+        let mut client = mock_client(&server).await;
+        client.default_market = Some(Market::DE);
+        Mock::given(method("GET"))
+            .and(path("/v1/recommendations"))
+            .and(query_param("market", "DE"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({ "seeds": [], "tracks": [track_json("track1")] })
+                )
+            )
+            .expect(1)
+            .mount(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/tracks"))
+            .and(query_param("market", "DE"))
+            .respond_with(DelayedTracks(Duration::ZERO))
+            .expect(1)
+            .mount(&server).await;
+
+        let mut request = RecommendationsRequest::new();
+        request.seed_genres = Some(vec!["pop".to_string()]);
+        let response = client.get_recommendations_hydrated(&request).await.unwrap();
+        assert_eq!(response.tracks.len(), 1);
+
+        request.market = Some(Market::FromToken);
+        let result = client.get_recommendations_hydrated(&request).await;
+        assert!(matches!(result, Err(RustyError::Io(e)) if e.kind() == std::io::ErrorKind::InvalidInput));
+    }
+
+    #[tokio::test]
+    async fn test_default_market_only_fills_missing_market() {
+        let server = MockServer::start().await;
+        let mut client = mock_client(&server).await;
+        client.default_market = Some(Market::SE);
+        for market in ["SE", "US"] {
+            Mock::given(method("GET"))
+                .and(path("/v1/artists/artist1/top-tracks"))
+                .and(query_param("market", market))
+                .respond_with(
+                    ResponseTemplate::new(200).set_body_json(serde_json::json!({ "tracks": [] }))
+                )
+                .expect(1)
+                .mount(&server).await;
+        }
+
+        client.get_artist_top_tracks("artist1", None).await.unwrap();
+        client.get_artist_top_tracks("artist1", Some(Market::US)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_top_tracks_market_defaults_to_us() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/artists/artist1/top-tracks"))
+            .and(query_param("market", "US"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "tracks": [] })))
+            .expect(1)
+            .mount(&server).await;
+
+        let top_tracks = client.get_artist_top_tracks("artist1", None).await.unwrap();
+        assert!(top_tracks.tracks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_from_token_market_is_rejected() {
+        let client = SpotifyClientCredentials::new(
+            "client_id".to_string(),
+            "client_secret".to_string()
+        );
+        let ids = ["track1".to_string()];
+
+        let result = client.get_several_tracks(&ids, Some(Market::FromToken)).await;
+        assert!(matches!(result, Err(RustyError::Io(e)) if e.kind() == std::io::ErrorKind::InvalidInput));
+        let result = client.get_artist_top_tracks("artist1", Some(Market::FromToken)).await;
+        assert!(matches!(result, Err(RustyError::Io(e)) if e.kind() == std::io::ErrorKind::InvalidInput));
+    }
+
+    #[tokio::test]
+    async fn test_get_tracks_map_omits_unresolved_ids() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        let mut relinked_track = track_json("track3");
+        relinked_track["linked_from"] = serde_json::json!({
+            "external_urls": { "spotify": "https://open.spotify.com/track/track2" },
+            "href": "https://api.spotify.com/v1/tracks/track2",
+            "id": "track2",
+            "type": "track",
+            "uri": "spotify:track:track2"
+        });
+        Mock::given(method("GET"))
+            .and(path("/v1/tracks"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({ "tracks": [track_json("track1"), relinked_track, null] })
+                )
+            )
+            .mount(&server).await;
+
+        let ids = ["track1".to_string(), "track2".to_string(), "unknown".to_string()];
+        let tracks = client.get_tracks_map(&ids, Some(Market::US)).await.unwrap();
+
+        assert_eq!(tracks.len(), 2);
+        assert_eq!(tracks["track1"].id, "track1");
+        assert_eq!(tracks["track2"].id, "track3");
+        assert!(!tracks.contains_key("unknown"));
+    }
+
+    #[tokio::test]
+    async fn test_oversized_response_is_rejected() {
+        let server = MockServer::start().await;
+        let mut client = mock_client(&server).await;
+        client.max_response_size = 64;
+        Mock::given(method("GET"))
+            .and(path("/v1/albums/album1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(album_json("album1")))
+            .mount(&server).await;
+
+        let result = client.get_album("album1").await;
+        assert!(matches!(result, Err(RustyError::ResponseTooLarge(64))));
+        assert!(!client.cache_contains("/albums/album1").await);
+
+        client.max_response_size = DEFAULT_MAX_RESPONSE_SIZE;
+        assert!(client.get_album("album1").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_get_all_artist_albums_follows_next_pages() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        let first_path = "/v1/artists/artist1/albums";
+        let next = format!("{}{first_path}?limit=50&include_groups=album,single&offset=2", server.uri());
+        Mock::given(method("GET"))
+            .and(path(first_path))
+            .and(wiremock::matchers::query_param("include_groups", "album,single"))
+            .and(wiremock::matchers::query_param_is_missing("offset"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({
+                        "href": format!("{}{first_path}", server.uri()),
+                        "items": [simplified_album_json("album1"), simplified_album_json("album2")],
+                        "limit": 2,
+                        "next": next,
+                        "offset": 0,
+                        "previous": null,
+                        "total": 3
+                    })
+                )
+            )
+            .expect(1)
+            .mount(&server).await;
+        Mock::given(method("GET"))
+            .and(path(first_path))
+            .and(wiremock::matchers::query_param("offset", "2"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({
+                        "href": next,
+                        "items": [simplified_album_json("album3")],
+                        "limit": 2,
+                        "next": null,
+                        "offset": 2,
+                        "previous": format!("{}{first_path}", server.uri()),
+                        "total": 3
+                    })
+                )
+            )
+            .expect(1)
+            .mount(&server).await;
+
+        let albums = client
+            .get_all_artist_albums("artist1", &[AlbumGroup::Album, AlbumGroup::Single], None).await
+            .unwrap();
+
+        let ids: Vec<&str> = albums
+            .iter()
+            .map(|album| album.id.as_str())
+            .collect();
+        assert_eq!(ids, ["album1", "album2", "album3"]);
+    }
+
+    #[tokio::test]
+    async fn test_get_all_album_tracks_follows_next_pages() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        let first_path = "/v1/albums/album1/tracks";
+        let next = format!("{}{first_path}?limit=50&offset=50", server.uri());
+        let first_page: Vec<Value> = (0..50).map(|i| simplified_track_json(&format!("track{i}"))).collect();
+        Mock::given(method("GET"))
+            .and(path(first_path))
+            .and(wiremock::matchers::query_param("limit", "50"))
+            .and(wiremock::matchers::query_param_is_missing("offset"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({
+                        "href": format!("{}{first_path}", server.uri()),
+                        "items": first_page,
+                        "limit": 50,
+                        "next": next,
+                        "offset": 0,
+                        "previous": null,
+                        "total": 51
+                    })
+                )
+            )
+            .expect(1)
+            .mount(&server).await;
+        Mock::given(method("GET"))
+            .and(path(first_path))
+            .and(wiremock::matchers::query_param("offset", "50"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({
+                        "href": next,
+                        "items": [simplified_track_json("track50")],
+                        "limit": 50,
+                        "next": null,
+                        "offset": 50,
+                        "previous": format!("{}{first_path}", server.uri()),
+                        "total": 51
+                    })
+                )
+            )
+            .expect(1)
+            .mount(&server).await;
+
+        let tracks = client.get_all_album_tracks("album1", None).await.unwrap();
+
+        assert_eq!(tracks.len(), 51);
+        assert_eq!(tracks[0].id, "track0");
+        assert_eq!(tracks[50].id, "track50");
+    }
+
+    #[tokio::test]
+    async fn test_paginate_follows_next_pages_and_yields_errors() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        let first_path = "/v1/albums/album1/tracks";
+        for (offset, items, next) in [
+            ("0", vec!["track0", "track1"], Some("2")),
+            ("2", vec!["track2"], Some("3")),
+        ] {
+            Mock::given(method("GET"))
+                .and(path(first_path))
+                .and(wiremock::matchers::query_param("offset", offset))
+                .respond_with(
+                    ResponseTemplate::new(200).set_body_json(
+                        serde_json::json!({
+                            "href": format!("{}{first_path}?offset={offset}", server.uri()),
+                            "items": items.into_iter().map(simplified_track_json).collect::<Vec<_>>(),
+                            "limit": 2,
+                            "next": next.map(|next| format!("{}{first_path}?offset={next}", server.uri())),
+                            "offset": offset.parse::<u32>().unwrap(),
+                            "previous": null,
+                            "total": 4
+                        })
+                    )
+                )
+                .expect(1)
+                .mount(&server).await;
+        }
+        Mock::given(method("GET"))
+            .and(path(first_path))
+            .and(wiremock::matchers::query_param("offset", "3"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(1)
+            .mount(&server).await;
+
+        let items: Vec<RustyResult<SimplifiedTrack>> = client
+            .paginate("/albums/album1/tracks?offset=0")
+            .collect().await;
+
+        let ids: Vec<&str> = items
+            .iter()
+            .filter_map(|item| item.as_ref().ok())
+            .map(|track| track.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["track0", "track1", "track2"]);
+        assert_eq!(items.len(), 4);
+        assert!(items[3].is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_all_album_tracks_stops_on_repeated_next() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        let first_path = "/v1/albums/album1/tracks";
+        // Both links point back at the first page, under a different URL for the second one
+        for (offset, next) in [
+            (None, format!("{}{first_path}?offset=0&limit=50", server.uri())),
+            (Some("0"), format!("{}{first_path}?limit=50&offset=0", server.uri())),
+        ] {
+            let mock = Mock::given(method("GET")).and(path(first_path));
+            let mock = match offset {
+                Some(offset) => mock.and(wiremock::matchers::query_param("offset", offset)),
+                None => mock.and(wiremock::matchers::query_param_is_missing("offset")),
+            };
+            mock.respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({
+                        "href": format!("{}{first_path}", server.uri()),
+                        "items": [simplified_track_json("track0")],
+                        "limit": 50,
+                        "next": next,
+                        "offset": 0,
+                        "previous": null,
+                        "total": 2
+                    })
+                )
+            )
+                .mount(&server).await;
+        }
+
+        let tracks = client.get_all_album_tracks("album1", None).await.unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(tracks.len(), 1);
+        assert_eq!(requests.iter().filter(|request| request.url.path() == first_path).count(), 2);
+    }
+
+    fn simplified_playlist_json(playlist_id: &str) -> Value {
+        serde_json::json!({
+            "collaborative": false,
+            "description": "",
+            "external_urls": { "spotify": format!("https://open.spotify.com/playlist/{playlist_id}") },
+            "href": format!("https://api.spotify.com/v1/playlists/{playlist_id}"),
+            "id": playlist_id,
+            "images": [],
+            "name": "Playlist Name",
+            "owner": { "id": "spotify", "type": "user" },
+            "public": true,
+            "snapshot_id": "snapshot",
+            "tracks": {
+                "href": format!("https://api.spotify.com/v1/playlists/{playlist_id}/tracks"),
+                "total": 10
+            },
+            "type": "playlist",
+            "uri": format!("spotify:playlist:{playlist_id}")
+        })
+    }
+
+    /// Responds to `/search?type=playlist&..` with a page of `total` playlists.
+    struct PlaylistSearch {
+        total: usize,
+    }
+
+    impl wiremock::Respond for PlaylistSearch {
+        fn respond(&self, request: &wiremock::Request) -> ResponseTemplate {
+            let param = |name: &str| {
+                request.url
+                    .query_pairs()
+                    .find(|(key, _)| key == name)
+                    .and_then(|(_, value)| value.parse::<usize>().ok())
+                    .unwrap_or_default()
+            };
+            let (limit, offset) = (param("limit"), param("offset"));
+            let end = (offset + limit).min(self.total);
+            let items: Vec<Value> = (offset..end)
+                .map(|i| simplified_playlist_json(&format!("playlist{i}")))
+                .collect();
+            let next = (end < self.total).then_some("https://api.spotify.com/v1/search?next");
+            ResponseTemplate::new(200).set_body_json(
+                serde_json::json!({
+                    "playlists": {
+                        "href": "https://api.spotify.com/v1/search",
+                        "items": items,
+                        "limit": limit,
+                        "next": next,
+                        "offset": offset,
+                        "previous": null,
+                        "total": self.total
+                    }
+                })
+            )
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_encodes_query_and_types() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/search"))
+            .and(wiremock::matchers::query_param("q", "daft punk & friends"))
+            .and(wiremock::matchers::query_param("type", "album,artist"))
+            .and(wiremock::matchers::query_param("limit", "5"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({
+                        "albums": {
+                            "href": "https://api.spotify.com/v1/search",
+                            "items": [simplified_album_json("album1")],
+                            "limit": 5,
+                            "next": null,
+                            "offset": 0,
+                            "previous": null,
+                            "total": 1
+                        },
+                        "artists": {
+                            "href": "https://api.spotify.com/v1/search",
+                            "items": [artist_json("artist1")],
+                            "limit": 5,
+                            "next": null,
+                            "offset": 0,
+                            "previous": null,
+                            "total": 1
+                        }
+                    })
+                )
+            )
+            .expect(1)
+            .mount(&server).await;
+
+        let results = client
+            .search("daft punk & friends", &[SearchType::Album, SearchType::Artist], None, Some(5), None).await
+            .unwrap();
+
+        assert_eq!(results.albums.unwrap().items[0].id, "album1");
+        assert_eq!(results.artists.unwrap().items[0].id, "artist1");
+        assert!(results.tracks.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_search_without_types_is_rejected() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+
+        let result = client.search("daft punk", &[], None, None, None).await;
+        assert!(matches!(result, Err(RustyError::Io(_))));
+        assert!(server.received_requests().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_search_all_stops_at_max_results() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/search"))
+            .respond_with(PlaylistSearch { total: 500 })
+            .expect(3)
+            .mount(&server).await;
+
+        let playlists = client.search_all("lofi", SearchType::Playlist, None, 120).await.unwrap();
+
+        assert_eq!(playlists.len(), 120);
+        assert!(matches!(&playlists[119], SearchItem::Playlist(playlist) if playlist.id == "playlist119"));
+    }
+
+    #[tokio::test]
+    async fn test_search_all_reaches_the_offset_cap() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/search"))
+            .respond_with(PlaylistSearch { total: 5000 })
+            .expect(20)
+            .mount(&server).await;
+
+        let playlists = client.search_all("lofi", SearchType::Playlist, None, 1000).await.unwrap();
+
+        assert_eq!(playlists.len(), 1000);
+    }
+
+    #[tokio::test]
+    async fn test_search_all_rejects_max_results_past_the_offset_cap() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+
+        let result = client.search_all("lofi", SearchType::Playlist, None, 2000).await;
+
+        let Err(RustyError::Io(e)) = result else {
+            panic!("expected an invalid input error, got {result:?}");
+        };
+        assert_eq!(e.kind(), std::io::ErrorKind::InvalidInput);
+        assert!(e.to_string().contains("1000"));
+        assert!(server.received_requests().await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_from_env_with_names_reports_missing_variable() {
+        env::set_var("RUSTYSPOTY_TEST_CLIENT_ID", "client_id");
+        env::remove_var("RUSTYSPOTY_TEST_MISSING_SECRET");
+
+        let result = SpotifyClientCredentials::from_env_with_names(
+            "RUSTYSPOTY_TEST_CLIENT_ID",
+            "RUSTYSPOTY_TEST_MISSING_SECRET"
+        );
+        assert!(
+            matches!(result, Err(RustyError::Io(e)) if e.to_string().contains("`RUSTYSPOTY_TEST_MISSING_SECRET`"))
+        );
+
+        env::set_var("RUSTYSPOTY_TEST_CLIENT_SECRET", "client_secret");
+        let result = SpotifyClientCredentials::from_env_with_names(
+            "RUSTYSPOTY_TEST_CLIENT_ID",
+            "RUSTYSPOTY_TEST_CLIENT_SECRET"
+        );
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_get_audio_features_is_cached() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/audio-features/track1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(audio_features_json("track1")))
+            .expect(1)
+            .mount(&server).await;
+
+        let features = client.get_audio_features("track1").await.unwrap();
+        assert_eq!(features.key, 9);
+        assert!((features.tempo - 118.211).abs() < 1e-3);
+
+        let cached = client.get_audio_features("track1").await.unwrap();
+        assert_eq!(cached.id, "track1");
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_playlist_refetches_only_that_playlist() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        client.update_cache("/playlists/playlist1".to_string(), Value::Null).await;
+        client.update_cache("/playlists/playlist1/tracks?offset=100".to_string(), Value::Null).await;
+        client.update_cache("/playlists/playlist10".to_string(), Value::Null).await;
+
+        assert_eq!(client.invalidate_playlist("playlist1").await, 2);
+        assert!(!client.cache_contains("/playlists/playlist1").await);
+        assert!(client.cache_contains("/playlists/playlist10").await);
+    }
+
+    #[tokio::test]
+    async fn test_get_several_audio_features_reuses_single_fetches() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/audio-features/track1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(audio_features_json("track1")))
+            .expect(1)
+            .mount(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/audio-features"))
+            .and(wiremock::matchers::query_param("ids", "track2,unknown"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({ "audio_features": [audio_features_json("track2"), null] })
+                )
+            )
+            .expect(1)
+            .mount(&server).await;
+
+        client.get_audio_features("track1").await.unwrap();
+        let ids = ["track1".to_string(), "track2".to_string(), "unknown".to_string()];
+        let features = client.get_several_audio_features(&ids).await.unwrap();
+
+        assert_eq!(features.len(), 3);
+        assert_eq!(features[0].as_ref().map(|f| f.id.as_str()), Some("track1"));
+        assert_eq!(features[1].as_ref().map(|f| f.id.as_str()), Some("track2"));
+        assert!(features[2].is_none());
+        assert!(client.cache_contains("/audio-features/track2").await);
+    }
+
+    #[tokio::test]
+    async fn test_get_audio_analysis_uncached_skips_cache() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        let interval = serde_json::json!({ "start": 0.49567, "duration": 2.18749, "confidence": 0.925 });
+        Mock::given(method("GET"))
+            .and(path("/v1/audio-analysis/track1"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({
+                        "meta": { "analyzer_version": "4.0.0", "status_code": 0 },
+                        "track": {
+                            "num_samples": 4585515,
+                            "duration": 207.95985,
+                            "loudness": -5.883,
+                            "tempo": 118.211,
+                            "tempo_confidence": 0.73,
+                            "time_signature": 4,
+                            "time_signature_confidence": 0.994,
+                            "key": 9,
+                            "key_confidence": 0.408,
+                            "mode": 0,
+                            "mode_confidence": 0.485,
+                            "codestring": "eJxVnAmS5DgOBL..."
+                        },
+                        "bars": [interval],
+                        "beats": [interval, interval],
+                        "sections": [],
+                        "segments": [{
+                            "start": 0.70154,
+                            "duration": 0.19891,
+                            "confidence": 0.435,
+                            "loudness_start": -23.053,
+                            "loudness_max": -14.25,
+                            "loudness_max_time": 0.07305,
+                            "pitches": [0.212, 0.141, 0.294],
+                            "timbre": [42.115, 64.373, -0.233]
+                        }],
+                        "tatums": [interval]
+                    })
+                )
+            )
+            .expect(2)
+            .mount(&server).await;
+
+        let analysis = client.get_audio_analysis_uncached("track1").await.unwrap();
+        assert_eq!(analysis.beats.len(), 2);
+        assert_eq!(analysis.segments[0].loudness_end, None);
+        assert!(!client.cache_contains("/audio-analysis/track1").await);
+
+        client.get_audio_analysis("track1").await.unwrap();
+        assert!(client.cache_contains("/audio-analysis/track1").await);
+    }
+
+    #[tokio::test]
+    async fn test_get_several_episodes_in_market() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/episodes"))
+            .and(wiremock::matchers::query_param("ids", "episode1,unknown"))
+            .and(wiremock::matchers::query_param("market", "US"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({ "episodes": [episode_json("episode1"), null] })
+                )
+            )
+            .expect(1)
+            .mount(&server).await;
+
+        let ids = ["episode1".to_string(), "unknown".to_string()];
+        let episodes = client.get_several_episodes(&ids, Some(Market::US)).await.unwrap();
+        assert_eq!(episodes.episodes.len(), 1);
+        assert_eq!(episodes.episodes[0].languages, ["en"]);
+
+        let too_many: Vec<String> = (0..51).map(|i| format!("episode{i}")).collect();
+        assert!(matches!(client.get_several_episodes(&too_many, None).await, Err(RustyError::Io(_))));
+    }
+
+    #[tokio::test]
+    async fn test_get_show_episodes_clamps_limit() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/shows/show1/episodes"))
+            .and(wiremock::matchers::query_param("limit", "50"))
+            .and(wiremock::matchers::query_param("offset", "0"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({
+                        "href": "https://api.spotify.com/v1/shows/show1/episodes",
+                        "items": [episode_json("episode1")],
+                        "limit": 50,
+                        "next": null,
+                        "offset": 0,
+                        "previous": null,
+                        "total": 1
+                    })
+                )
+            )
+            .expect(1)
+            .mount(&server).await;
+
+        let episodes = client.get_show_episodes("show1", None, Some(500), Some(-3)).await.unwrap();
+        assert_eq!(episodes.items[0].id, "episode1");
+    }
+
+    #[tokio::test]
+    async fn test_get_category_skips_missing_options() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/browse/categories/dinner"))
+            .and(wiremock::matchers::query_param("locale", "es_MX"))
+            .and(wiremock::matchers::query_param_is_missing("country"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({
+                        "href": "https://api.spotify.com/v1/browse/categories/dinner",
+                        "icons": [],
+                        "id": "dinner",
+                        "name": "Cena"
+                    })
+                )
+            )
+            .expect(1)
+            .mount(&server).await;
+
+        let category = client.get_category("dinner", None, Some("es_MX")).await.unwrap();
+        assert_eq!(category.name, "Cena");
+        assert!(client.cache_contains("/browse/categories/dinner?locale=es_MX").await);
+    }
+
+    /// A [`CacheBackend`] recording the values stored in it and whether it was flushed.
+    #[derive(Default)]
+    struct RecordingCache {
+        values: Arc<std::sync::Mutex<HashMap<String, (Value, Duration)>>>,
+        flushed: Arc<std::sync::atomic::AtomicBool>,
+    }
+
+    impl CacheBackend for RecordingCache {
+        fn get(&self, key: &str) -> Option<Value> {
+            self.values.lock().unwrap().get(key).map(|(value, _)| value.clone())
+        }
+
+        fn set(&self, key: String, value: Value, ttl: Duration) {
+            self.values.lock().unwrap().insert(key, (value, ttl));
+        }
+
+        fn remove(&self, key: &str) {
+            self.values.lock().unwrap().remove(key);
+        }
+
+        fn clear(&self) {
+            self.values.lock().unwrap().clear();
+        }
+
+        fn len(&self) -> usize {
+            self.values.lock().unwrap().len()
+        }
+
+        fn flush(&self) -> std::pin::Pin<Box<dyn std::future::Future<Output = ()> + Send + '_>> {
+            Box::pin(async move {
+                self.flushed.store(true, std::sync::atomic::Ordering::SeqCst);
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cache_backend_stores_responses_and_is_flushed_on_shutdown() {
+        let server = MockServer::start().await;
+        mount_token(&server).await;
+        let cache = RecordingCache::default();
+        let values = Arc::clone(&cache.values);
+        let flushed = Arc::clone(&cache.flushed);
+        let mut client = SpotifyClientCredentials::builder(
+            "client_id".to_string(),
+            "client_secret".to_string()
+        )
+            .with_cache_ttl(Duration::from_secs(30))
+            .with_cache_backend(cache)
+            .build()
+            .unwrap();
+        client.use_mock_server(&server.uri());
+        Mock::given(method("GET"))
+            .and(path("/v1/tracks/track1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(track_json("track1")))
+            .expect(1)
+            .mount(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/markets"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "markets": ["SE"] }))
+            )
+            .expect(1)
+            .mount(&server).await;
+
+        client.get_track("track1").await.unwrap();
+        client.get_track("track1").await.unwrap();
+        client.get_available_markets().await.unwrap();
+        assert_eq!(client.cache_len().await, 2);
+        {
+            let values = values.lock().unwrap();
+            assert_eq!(values["/tracks/track1"].1, Duration::from_secs(30));
+            assert_eq!(values["/markets"].1, LONG_CACHE_TTL);
+        }
+
+        client.shutdown().await;
+        assert!(flushed.load(std::sync::atomic::Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_cache_max_entries_evicts_least_recently_used_response() {
+        let server = MockServer::start().await;
+        mount_token(&server).await;
+        let mut client = SpotifyClientCredentials::builder(
+            "client_id".to_string(),
+            "client_secret".to_string()
+        )
+            .with_cache_max_entries(1)
+            .build()
+            .unwrap();
+        client.use_mock_server(&server.uri());
+        for (track_id, expected) in [("track1", 2), ("track2", 1)] {
+            Mock::given(method("GET"))
+                .and(path(format!("/v1/tracks/{track_id}")))
+                .respond_with(ResponseTemplate::new(200).set_body_json(track_json(track_id)))
+                .expect(expected)
+                .mount(&server).await;
+        }
+
+        client.get_track("track1").await.unwrap();
+        client.get_track("track2").await.unwrap();
+        assert!(!client.cache_contains("/tracks/track1").await);
+        assert!(client.cache_contains("/tracks/track2").await);
+        client.get_track("track1").await.unwrap();
+    }
+
+    #[test]
+    fn test_cache_ttl_for_picks_lifetime_by_path() {
+        assert_eq!(cache_ttl_for("/markets"), Some(LONG_CACHE_TTL));
+        assert_eq!(cache_ttl_for("/recommendations/available-genre-seeds"), Some(LONG_CACHE_TTL));
+        assert_eq!(cache_ttl_for("/browse/categories?limit=20&offset=0"), Some(LONG_CACHE_TTL));
+        assert_eq!(cache_ttl_for("/browse/categories/dinner/playlists?limit=20"), None);
+        assert_eq!(cache_ttl_for("/browse/new-releases?limit=20&offset=0"), None);
+    }
+
+    #[tokio::test]
+    async fn test_with_cache_ttl_expires_responses() {
+        let server = MockServer::start().await;
+        mount_token(&server).await;
+        let mut client = SpotifyClientCredentials::with_cache_ttl(
+            "client_id".to_string(),
+            "client_secret".to_string(),
+            Duration::from_millis(50)
+        );
+        client.use_mock_server(&server.uri());
+        Mock::given(method("GET"))
+            .and(path("/v1/tracks/track1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(track_json("track1")))
+            .expect(2)
+            .mount(&server).await;
+
+        client.get_track("track1").await.unwrap();
+        client.get_track("track1").await.unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        client.get_track("track1").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_refetches_the_response() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/tracks/track1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(track_json("track1")))
+            .expect(2)
+            .mount(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/tracks/track2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(track_json("track2")))
+            .expect(1)
+            .mount(&server).await;
+
+        client.get_track("track1").await.unwrap();
+        client.get_track("track2").await.unwrap();
+        client.invalidate("/tracks/track1").await;
+        assert!(client.cache_contains("/tracks/track2").await);
+        client.get_track("track1").await.unwrap();
+        client.get_track("track2").await.unwrap();
+
+        client.clear_cache().await;
+        assert_eq!(client.cache_len().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_cache_removes_expired_responses() {
+        let server = MockServer::start().await;
+        mount_token(&server).await;
+        let mut client = SpotifyClientCredentials::with_cache_ttl(
+            "client_id".to_string(),
+            "client_secret".to_string(),
+            Duration::from_millis(50)
+        );
+        client.use_mock_server(&server.uri());
+        for track_id in ["track1", "track2"] {
+            Mock::given(method("GET"))
+                .and(path(format!("/v1/tracks/{track_id}")))
+                .respond_with(ResponseTemplate::new(200).set_body_json(track_json(track_id)))
+                .mount(&server).await;
+            client.get_track(track_id).await.unwrap();
+        }
+        assert_eq!(client.cleanup_cache().await, 0);
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(client.cache_len().await, 2);
+        assert_eq!(client.cleanup_cache().await, 2);
+        assert_eq!(client.cache_len().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_get_available_markets_is_cached() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/markets"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({ "markets": ["CA", "BR", "SE"] })
+                )
+            )
+            .expect(1)
+            .mount(&server).await;
+
+        assert_eq!(client.get_available_markets().await.unwrap(), vec!["CA", "BR", "SE"]);
+        assert_eq!(client.get_available_markets().await.unwrap(), vec!["CA", "BR", "SE"]);
+    }
+
+    #[tokio::test]
+    async fn test_get_featured_playlists_passes_timestamp() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/browse/featured-playlists"))
+            .and(wiremock::matchers::query_param("timestamp", "2014-10-23T09:00:00"))
+            .and(wiremock::matchers::query_param("country", "SE"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({
+                        "message": "Monday morning music, coming right up!",
+                        "playlists": {
+                            "href": "https://api.spotify.com/v1/browse/featured-playlists",
+                            "items": [simplified_playlist_json("playlist1")],
+                            "limit": 20,
+                            "next": null,
+                            "offset": 0,
+                            "previous": null,
+                            "total": 1
+                        }
+                    })
+                )
+            )
+            .expect(1)
+            .mount(&server).await;
+
+        let featured = client
+            .get_featured_playlists(Some("SE"), None, Some("2014-10-23T09:00:00"), None, None).await
+            .unwrap();
+        assert_eq!(featured.message, "Monday morning music, coming right up!");
+        assert_eq!(featured.playlists.items[0].id, "playlist1");
+    }
+
+    #[tokio::test]
+    async fn test_html_response_is_rejected_with_clear_error() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/albums/album1"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_raw(
+                    "<!DOCTYPE html><html><body>Please log in to the network</body></html>",
+                    "text/html; charset=utf-8"
+                )
+            )
+            .mount(&server).await;
+
+        let err = client.get_album("album1").await.unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("expected JSON, got text/html"), "{message}");
+        assert!(message.contains("body starts with <!DOCTYPE html>"), "{message}");
+    }
+
+    #[test]
+    fn test_to_query_string_encodes_seeds() {
+        let client = SpotifyClientCredentials::new(
+            "client_id".to_string(),
+            "client_secret".to_string()
+        );
+        let mut request = RecommendationsRequest::new();
+        request.add_seed_genre("drum-and-bass").unwrap().add_seed_genre("r&b soul").unwrap();
+        request.limit = Some(10);
+
+        let query_string = client.to_query_string(&request.to_json().unwrap());
+        assert_eq!(query_string, "limit=10&seed_genres=drum-and-bass,r%26b+soul");
+    }
+
+    #[test]
+    fn test_is_json_content_type() {
+        assert!(is_json_content_type("application/json"));
+        assert!(is_json_content_type("application/json; charset=utf-8"));
+        assert!(is_json_content_type("application/problem+json"));
+        assert!(!is_json_content_type("text/html; charset=utf-8"));
+    }
+
+    #[tokio::test]
+    async fn test_get_album_if_modified() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/albums/album1"))
+            .and(header("If-None-Match", "\"v1\""))
+            .respond_with(ResponseTemplate::new(304))
+            .mount(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/albums/album1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(album_json("album1"))
+                    .insert_header("ETag", "\"v2\"")
+            )
+            .mount(&server).await;
+
+        let current = Validators { etag: Some("\"v1\"".to_string()), last_modified: None };
+        let result = client.get_album_if_modified("album1", &current).await.unwrap();
+        assert!(matches!(result, Conditional::NotModified));
+
+        let stale = Validators { etag: Some("\"v0\"".to_string()), last_modified: None };
+        match client.get_album_if_modified("album1", &stale).await.unwrap() {
+            Conditional::Modified(album, validators) => {
+                assert_eq!(album.id, "album1");
+                assert_eq!(validators.etag.as_deref(), Some("\"v2\""));
+            }
+            Conditional::NotModified => panic!("expected the album to be downloaded again"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_album_if_modified_accepts_uris() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/albums/album1"))
+            .and(header("If-None-Match", "\"v1\""))
+            .respond_with(ResponseTemplate::new(304))
+            .expect(1)
+            .mount(&server).await;
+
+        let current = Validators { etag: Some("\"v1\"".to_string()), last_modified: None };
+        let result = client.get_album_if_modified("spotify:album:album1", &current).await.unwrap();
+        assert!(matches!(result, Conditional::NotModified));
+    }
+
+    #[tokio::test]
+    async fn test_cache_sweeper_removes_expired_entries_until_dropped() {
+        let client = SpotifyClientCredentials::builder(
+            "client_id".to_string(),
+            "client_secret".to_string()
+        )
+            .with_cache_sweep_interval(Duration::from_millis(20))
+            .build()
+            .unwrap();
+        client.cache.set("/albums/album1".to_string(), album_json("album1"), Duration::ZERO);
+        client.update_cache("/albums/album2".to_string(), album_json("album2")).await;
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(client.cache_len().await, 1);
+
+        // Dropping the client aborts the task, which releases its handle on the cache
+        let cache = Arc::downgrade(&client.cache);
+        drop(client);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(cache.upgrade().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_stops_cache_sweeper() {
+        let client = SpotifyClientCredentials::builder(
+            "client_id".to_string(),
+            "client_secret".to_string()
+        )
+            .with_cache_sweep_interval(Duration::from_millis(20))
+            .build()
+            .unwrap();
+
+        let cache = Arc::downgrade(&client.cache);
+        client.shutdown().await;
+        assert!(cache.upgrade().is_none());
+    }
+
+    #[test]
+    fn test_cache_sweeper_requires_runtime() {
+        let result = SpotifyClientCredentials::builder(
+            "client_id".to_string(),
+            "client_secret".to_string()
+        )
+            .with_cache_sweep_interval(Duration::from_secs(60))
+            .build();
+        assert!(matches!(result, Err(RustyError::Unexpected(_))));
+    }
+
+    #[tokio::test]
+    async fn test_get_several_artists_detailed_reports_missing_ids() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/artists"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({ "artists": [artist_json("artist1"), null] })
+                )
+            )
+            .mount(&server).await;
+
+        let ids = ["artist1".to_string(), "unknown".to_string()];
+        let result = client.get_several_artists_detailed(&ids).await.unwrap();
+
+        assert_eq!(result.found["artist1"].id, "artist1");
+        assert_eq!(result.missing, ["unknown"]);
+    }
+
+    #[tokio::test]
+    async fn test_new_releases_past_the_offset_cap_are_rejected() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+
+        let result = client.get_new_album_releases(Some(50), Some(960)).await;
+        assert!(
+            matches!(result, Err(RustyError::Io(e)) if e.to_string().contains("offset 960 + limit 50"))
+        );
+        assert!(server.received_requests().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_album_tracks_in_market_is_relinked() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/albums/album1/tracks"))
+            .and(wiremock::matchers::query_param("market", "DE"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({
+                        "href": "https://api.spotify.com/v1/albums/album1/tracks?market=DE",
+                        "items": [{
+                            "artists": [],
+                            "disc_number": 1,
+                            "duration_ms": 180000,
+                            "explicit": false,
+                            "external_urls": { "spotify": "https://open.spotify.com/track/track2" },
+                            "href": "https://api.spotify.com/v1/tracks/track2",
+                            "id": "track2",
+                            "name": "Track Name",
+                            "preview_url": null,
+                            "track_number": 1,
+                            "is_playable": true,
+                            "linked_from": {
+                                "external_urls": { "spotify": "https://open.spotify.com/track/track1" },
+                                "href": "https://api.spotify.com/v1/tracks/track1",
+                                "id": "track1",
+                                "type": "track",
+                                "uri": "spotify:track:track1"
+                            }
+                        }],
+                        "limit": 20,
+                        "next": null,
+                        "offset": 0,
+                        "previous": null,
+                        "total": 1
+                    })
+                )
+            )
+            .expect(1)
+            .mount(&server).await;
+
+        let tracks = client.get_album_tracks("album1", None, None, Some(Market::DE)).await.unwrap();
+        let track = &tracks.items[0];
+        assert_eq!(track.is_playable, Some(true));
+        assert_eq!(track.linked_from.as_ref().map(|linked| linked.id.as_str()), Some("track1"));
+        assert!(client.cache_contains("/albums/album1/tracks?limit=20&market=DE&offset=0").await);
+    }
+
+    #[tokio::test]
+    async fn test_get_album_tracks_pages_are_cached_separately() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        for offset in ["0", "50"] {
+            Mock::given(method("GET"))
+                .and(path("/v1/albums/album1/tracks"))
+                .and(wiremock::matchers::query_param("limit", "50"))
+                .and(wiremock::matchers::query_param("offset", offset))
+                .respond_with(
+                    ResponseTemplate::new(200).set_body_json(
+                        serde_json::json!({
+                            "href": "https://api.spotify.com/v1/albums/album1/tracks",
+                            "items": [simplified_track_json(&format!("track{offset}"))],
+                            "limit": 50,
+                            "next": null,
+                            "offset": offset.parse::<u32>().unwrap(),
+                            "previous": null,
+                            "total": 51
+                        })
+                    )
+                )
+                .expect(1)
+                .mount(&server).await;
+        }
+
+        let first = client.get_album_tracks("album1", Some(500), None, None).await.unwrap();
+        let second = client.get_album_tracks("album1", Some(50), Some(50), None).await.unwrap();
+        assert_eq!(first.items[0].id, "track0");
+        assert_eq!(second.items[0].id, "track50");
+        assert!(client.cache_contains("/albums/album1/tracks?limit=50&offset=0").await);
+        assert!(client.cache_contains("/albums/album1/tracks?limit=50&offset=50").await);
+    }
+
+    #[tokio::test]
+    async fn test_get_several_albums_only_fetches_uncached_ids_in_order() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        client.update_cache("/albums/album2".to_string(), album_json("album2")).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/albums"))
+            .and(wiremock::matchers::query_param("ids", "album1,album3"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({ "albums": [album_json("album1"), album_json("album3")] })
+                )
+            )
+            .expect(1)
+            .mount(&server).await;
+
+        let ids = ["album1".to_string(), "album2".to_string(), "album3".to_string()];
+        let response = client.get_several_albums(&ids).await.unwrap();
+
+        let fetched: Vec<&str> = response.albums
+            .iter()
+            .map(|album| album.id.as_str())
+            .collect();
+        assert_eq!(fetched, ["album1", "album2", "album3"]);
+    }
+
+    #[tokio::test]
+    async fn test_get_several_albums_all_cached_sends_no_request() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        client.update_cache("/albums/album1".to_string(), album_json("album1")).await;
+        client.update_cache("/albums/album2".to_string(), album_json("album2")).await;
+
+        let ids = ["album2".to_string(), "album1".to_string()];
+        let response = client.get_several_albums(&ids).await.unwrap();
+
+        let fetched: Vec<&str> = response.albums
+            .iter()
+            .map(|album| album.id.as_str())
+            .collect();
+        assert_eq!(fetched, ["album2", "album1"]);
+        assert!(server.received_requests().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_request_is_authorized_and_cached() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/albums/album1"))
+            .and(header("Authorization", "Bearer test_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(album_json("album1")))
+            .expect(1)
+            .mount(&server).await;
+
+        let album = client.get_album("album1").await.unwrap();
+        assert_eq!(album.id, "album1");
+
+        // The second call is served from the cache, `expect(1)` is verified on drop
+        let album = client.get_album_cached("album1").await.unwrap();
+        assert!(album.from_cache);
+    }
+
+    #[tokio::test]
+    async fn test_send_request_with_json_body_and_no_content() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        let body = serde_json::json!({ "ids": ["track1", "track2"] });
+        Mock::given(method("PUT"))
+            .and(path("/v1/me/tracks"))
+            .and(body_json(&body))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server).await;
+
+        let result: RustyResult<()> = client.send_spotify_request(
+            Method::PUT,
+            "/me/tracks",
+            Some(&body)
+        ).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_send_request_with_empty_body_as_option() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/me/player"))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&server).await;
+
+        let result = client.send_spotify_request::<Option<Value>, ()>(
+            Method::GET,
+            "/me/player",
+            None
+        ).await;
+        assert!(matches!(result, Ok(None)));
+    }
+
+    #[tokio::test]
+    async fn test_send_request_rate_limited() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+        Mock::given(method("DELETE"))
+            .and(path("/v1/me/tracks"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "7"))
+            .mount(&server).await;
+
+        let result = client.send_spotify_request::<(), ()>(Method::DELETE, "/me/tracks", None).await;
+        assert!(matches!(result, Err(RustyError::SpotifyRateLimited(7))));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_request_is_retried_after_waiting() {
+        let server = MockServer::start().await;
+        mount_token(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/albums/album1"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "1"))
+            .up_to_n_times(1)
+            .mount(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/albums/album1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(album_json("album1")))
+            .mount(&server).await;
+        let mut client = SpotifyClientCredentials::builder(
+            "client_id".to_string(),
+            "client_secret".to_string()
+        )
+            .with_rate_limit_retries(2)
+            .build()
+            .unwrap();
+        client.use_mock_server(&server.uri());
+
+        let start = Instant::now();
+        let album = client.get_album("album1").await.unwrap();
+        assert_eq!(album.id, "album1");
+        assert!(start.elapsed() >= Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_request_fails_once_retries_run_out() {
+        let server = MockServer::start().await;
+        mount_token(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/albums/album1"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+            .expect(2)
+            .mount(&server).await;
+        let mut client = SpotifyClientCredentials::builder(
+            "client_id".to_string(),
+            "client_secret".to_string()
+        )
+            .with_rate_limit_retries(1)
+            .build()
+            .unwrap();
+        client.use_mock_server(&server.uri());
+
+        let result = client.get_album("album1").await;
+        assert!(matches!(result, Err(RustyError::SpotifyRateLimited(0))));
+    }
+
+    #[tokio::test]
+    async fn test_server_error_is_retried_with_backoff() {
+        let server = MockServer::start().await;
+        mount_token(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/albums/album1"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .mount(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/albums/album1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(album_json("album1")))
+            .mount(&server).await;
+        let mut client = SpotifyClientCredentials::builder(
+            "client_id".to_string(),
+            "client_secret".to_string()
+        )
+            .with_retry_policy(RetryPolicy { max_retries: 3, base_delay: Duration::from_millis(20) })
+            .build()
+            .unwrap();
+        client.use_mock_server(&server.uri());
+
+        let start = Instant::now();
+        let album = client.get_album("album1").await.unwrap();
+        assert_eq!(album.id, "album1");
+        // Waited at least 20ms, then 40ms
+        assert!(start.elapsed() >= Duration::from_millis(60));
+    }
+
+    #[tokio::test]
+    async fn test_client_error_is_not_retried() {
+        let server = MockServer::start().await;
+        mount_token(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/albums/album1"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server).await;
+        let mut client = SpotifyClientCredentials::builder(
+            "client_id".to_string(),
+            "client_secret".to_string()
+        )
+            .with_retry_policy(RetryPolicy { max_retries: 3, base_delay: Duration::from_millis(20) })
+            .build()
+            .unwrap();
+        client.use_mock_server(&server.uri());
+
+        let result = client.get_album("album1").await;
+        assert!(matches!(result, Err(RustyError::NotFound(_))));
     }
 
     #[tokio::test]
     async fn test_client() {
-        let mut client = setup();
+        let client = setup();
 
         let genres_result = client.get_genre_seeds().await;
         assert!(genres_result.is_ok());
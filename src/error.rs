@@ -3,6 +3,11 @@
 use std::error::Error;
 use std::fmt;
 
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+use crate::auth_code_pkce::OAuthError;
+
 pub type RustyResult<T> = Result<T, RustyError>;
 
 /// A custom error type for the application, covering various error scenarios encountered.
@@ -10,8 +15,15 @@ pub type RustyResult<T> = Result<T, RustyError>;
 /// This enum encapsulates different kinds of errors that can occur in the application, including specific handling for rate limiting by the Spotify API.
 #[derive(Debug)]
 pub enum RustyError {
-    /// Represents errors that occur during network requests.
+    /// Represents errors that occur during network requests, other than the more specific
+    /// `Connect`, `Timeout` and `Decode` errors.
     Network(reqwest::Error),
+    /// Represents failures to connect to the server, e.g. a refused connection or a DNS failure.
+    Connect(reqwest::Error),
+    /// Represents requests that timed out.
+    Timeout(reqwest::Error),
+    /// Represents response bodies that could not be decoded.
+    Decode(reqwest::Error),
     /// Represents errors that occur while parsing JSON data.
     ParseJson(serde_json::Error),
     /// Represents errors with input or output data.
@@ -20,6 +32,25 @@ pub enum RustyError {
     TokenAuthentication(String),
     /// Represents being rate limited by the Spotify API and includes the duration to wait.
     SpotifyRateLimited(u64), // Duration in seconds to wait before retrying
+    /// Represents a playlist edit rejected because it was based on a stale `snapshot_id`.
+    /// The playlist should be refetched and the edit retried against the new snapshot.
+    SnapshotConflict(String),
+    /// Represents a response body larger than the client's maximum response size, in bytes.
+    ResponseTooLarge(usize),
+    /// Represents a resource that does not exist, e.g. an unknown track ID (HTTP 404), with the
+    /// message given by Spotify.
+    NotFound(String),
+    /// Represents a request whose access token was rejected, e.g. because it is invalid or was
+    /// revoked (HTTP 401), with the message given by Spotify.
+    Unauthorized(String),
+    /// Represents a request rejected by the Spotify Web API, with the HTTP status and the message
+    /// Spotify gave in its error body, e.g. `invalid id`.
+    SpotifyApi {
+        /// The HTTP status code of the response.
+        status: u16,
+        /// The error message from the response body.
+        message: String,
+    },
     /// Represents unexpected or miscellaneous errors.
     Unexpected(String),
 }
@@ -30,15 +61,72 @@ impl RustyError {
     }
 }
 
+/// Checks whether a failed request was rejected because of a playlist `snapshot_id` mismatch.
+///
+/// Spotify reports these as a 400 or 409 whose body mentions the snapshot.
+pub(crate) fn is_snapshot_conflict(status: StatusCode, body: &str) -> bool {
+    matches!(status, StatusCode::BAD_REQUEST | StatusCode::CONFLICT) &&
+        body.to_ascii_lowercase().contains("snapshot")
+}
+
+/// The body of a failed Spotify Web API response, e.g.
+/// `{"error": {"status": 400, "message": "invalid id"}}`.
+#[derive(Deserialize)]
+struct ErrorBody {
+    error: ErrorObject,
+}
+
+/// The error object of a failed Spotify Web API response.
+#[derive(Deserialize)]
+struct ErrorObject {
+    status: u16,
+    message: String,
+}
+
+/// Turns a failed Spotify Web API response into a `RustyError`.
+///
+/// Returns `RustyError::NotFound` for a 404 and `RustyError::Unauthorized` for a 401. Other
+/// statuses return `RustyError::SpotifyApi` with the message of Spotify's JSON error body, or
+/// `RustyError::Unexpected` with the status only if the body is not one.
+pub(crate) fn api_error(status: StatusCode, body: &str) -> RustyError {
+    let message = serde_json::from_str::<ErrorBody>(body).map(|body| body.error);
+    match status {
+        StatusCode::NOT_FOUND | StatusCode::UNAUTHORIZED => {
+            let message = message.map_or_else(|_| status.to_string(), |error| error.message);
+            if status == StatusCode::NOT_FOUND {
+                RustyError::NotFound(message)
+            } else {
+                RustyError::Unauthorized(message)
+            }
+        }
+        _ =>
+            match message {
+                Ok(error) => RustyError::SpotifyApi { status: error.status, message: error.message },
+                Err(_) => RustyError::Unexpected(format!("API request failed with status: {status}")),
+            }
+    }
+}
+
 impl fmt::Display for RustyError {
     /// Provides a human-readable description of the error.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             RustyError::Network(e) => write!(f, "network error: {e}"),
+            RustyError::Connect(e) => write!(f, "failed to connect: {e}"),
+            RustyError::Timeout(e) => write!(f, "request timed out: {e}"),
+            RustyError::Decode(e) => write!(f, "failed to decode response: {e}"),
             RustyError::ParseJson(e) => write!(f, "failed to parse data: {e}"),
             RustyError::TokenAuthentication(msg) => write!(f, "token authentication error: {msg}"),
             RustyError::SpotifyRateLimited(duration) =>
                 write!(f, "rate limited by Spotify API, retry after {duration} seconds"),
+            RustyError::SnapshotConflict(msg) =>
+                write!(f, "playlist snapshot is out of date: {msg}"),
+            RustyError::ResponseTooLarge(max_size) =>
+                write!(f, "response body exceeds the maximum size of {max_size} bytes"),
+            RustyError::NotFound(msg) => write!(f, "not found: {msg}"),
+            RustyError::Unauthorized(msg) => write!(f, "unauthorized: {msg}"),
+            RustyError::SpotifyApi { status, message } =>
+                write!(f, "Spotify API error {status}: {message}"),
             RustyError::Unexpected(msg) => write!(f, "an unexpected error occurred: {msg}"),
             RustyError::Io(e) => write!(f, "input/output error: {e}"),
         }
@@ -48,9 +136,19 @@ impl fmt::Display for RustyError {
 impl Error for RustyError {}
 
 impl From<reqwest::Error> for RustyError {
-    /// Converts `reqwest::Error` into `RustyError::Network`.
+    /// Converts `reqwest::Error` into `RustyError::Timeout`, `RustyError::Connect` or
+    /// `RustyError::Decode` when it is one of these, and into `RustyError::Network` otherwise.
     fn from(err: reqwest::Error) -> RustyError {
-        RustyError::Network(err)
+        // A connection attempt can time out too, in which case the timeout is the more useful kind
+        if err.is_timeout() {
+            RustyError::Timeout(err)
+        } else if err.is_connect() {
+            RustyError::Connect(err)
+        } else if err.is_decode() {
+            RustyError::Decode(err)
+        } else {
+            RustyError::Network(err)
+        }
     }
 }
 
@@ -61,8 +159,89 @@ impl From<serde_json::Error> for RustyError {
     }
 }
 
+impl From<OAuthError> for RustyError {
+    /// Converts `OAuthError` into the same variants as `reqwest::Error` for network failures,
+    /// and into `RustyError::TokenAuthentication` otherwise.
+    fn from(err: OAuthError) -> RustyError {
+        match err {
+            OAuthError::HttpError(err) => RustyError::from(err),
+            OAuthError::UrlParseError(err) => RustyError::TokenAuthentication(err.to_string()),
+            OAuthError::Base64DecodeError(err) => RustyError::TokenAuthentication(err.to_string()),
+            OAuthError::Other(msg) => RustyError::TokenAuthentication(msg),
+        }
+    }
+}
+
 impl From<std::io::Error> for RustyError {
     fn from(value: std::io::Error) -> Self {
         RustyError::Io(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use wiremock::{ matchers::method, Mock, MockServer, ResponseTemplate };
+
+    #[test]
+    fn test_is_snapshot_conflict() {
+        let body = r#"{"error":{"status":400,"message":"Invalid snapshot id"}}"#;
+        assert!(is_snapshot_conflict(StatusCode::BAD_REQUEST, body));
+        assert!(is_snapshot_conflict(StatusCode::CONFLICT, body));
+        assert!(!is_snapshot_conflict(StatusCode::NOT_FOUND, body));
+        assert!(
+            !is_snapshot_conflict(
+                StatusCode::BAD_REQUEST,
+                r#"{"error":{"status":400,"message":"invalid id"}}"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_api_error_reads_spotify_error_body() {
+        let body = r#"{"error":{"status":400,"message":"invalid id"}}"#;
+        let err = api_error(StatusCode::BAD_REQUEST, body);
+        assert!(
+            matches!(&err, RustyError::SpotifyApi { status: 400, message } if message == "invalid id")
+        );
+        assert_eq!(err.to_string(), "Spotify API error 400: invalid id");
+
+        let err = api_error(StatusCode::NOT_FOUND, r#"{"error":{"status":404,"message":"Non existing id"}}"#);
+        assert!(matches!(err, RustyError::NotFound(msg) if msg == "Non existing id"));
+
+        let err = api_error(StatusCode::UNAUTHORIZED, "");
+        assert!(matches!(err, RustyError::Unauthorized(msg) if msg.contains("401")));
+
+        let err = api_error(StatusCode::BAD_GATEWAY, "<html>Bad Gateway</html>");
+        assert!(matches!(err, RustyError::Unexpected(msg) if msg.contains("502")));
+    }
+
+    #[tokio::test]
+    async fn test_network_errors_are_classified() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("not json")
+                    .set_delay(Duration::from_millis(200))
+            )
+            .mount(&server).await;
+        let client = reqwest::Client::new();
+
+        let err = client
+            .get(server.uri())
+            .timeout(Duration::from_millis(50))
+            .send().await
+            .unwrap_err();
+        assert!(matches!(RustyError::from(err), RustyError::Timeout(_)));
+
+        let err = client.get(server.uri()).send().await.unwrap().json::<u32>().await.unwrap_err();
+        assert!(matches!(RustyError::from(err), RustyError::Decode(_)));
+
+        // Nothing listens on a port once its listener is dropped
+        let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let err = client.get(format!("http://127.0.0.1:{port}")).send().await.unwrap_err();
+        assert!(matches!(RustyError::from(err), RustyError::Connect(_)));
+    }
+}
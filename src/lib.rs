@@ -4,15 +4,33 @@ pub mod models;
 mod services;
 
 mod client_creds;
+mod api;
+mod builder;
 mod token_manager;
 mod error;
 mod cache;
+mod batch;
+mod rate_limit;
+mod retry;
 mod auth_code_pkce;
+mod user_client;
+#[cfg(feature = "mock")]
+mod mock;
 
 pub use self::{
     client_creds::*,
+    api::SpotifyApi,
+    builder::*,
     token_manager::*,
     error::*,
     services::*,
-    auth_code_pkce::SpotifyOAuth,
+    rate_limit::*,
+    retry::RetryPolicy,
+    cache::{ CacheBackend, Cached, Conditional, Validators },
+    batch::BatchResult,
+    auth_code_pkce::{ AccessTokenResponse, SpotifyOAuth },
+    user_client::SpotifyUserClient,
 };
+
+#[cfg(feature = "mock")]
+pub use self::mock::MockSpotifyClient;
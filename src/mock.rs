@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+
+use crate::{
+    models::{
+        album::Album,
+        artist::Artist,
+        market::Market,
+        playlist::Playlist,
+        recommendations::*,
+        search::{ SearchResults, SearchType },
+        track::Track,
+    },
+    RustyError,
+    RustyResult,
+    SpotifyApi,
+};
+
+/// A [`SpotifyApi`] implementation returning canned data, standing in for
+/// [`SpotifyClientCredentials`](crate::SpotifyClientCredentials) when testing applications built on
+/// this crate without network access.
+///
+/// Only available with the `mock` feature, typically enabled for tests only:
+///
+/// ```toml
+/// [dev-dependencies]
+/// rustyspoty = { git = "https://github.com/blu3eee/rustyspoty.git", features = ["mock"] }
+/// ```
+///
+/// Lookups of ids that were not registered fail with `RustyError::NotFound`, like a 404 from
+/// Spotify. Recommendations and searches fail with `RustyError::Unexpected` until a response is
+/// registered for them.
+///
+/// # Examples
+///
+/// ```
+/// # use rustyspoty::{ models::track::Track, MockSpotifyClient, RustyError, SpotifyApi };
+/// # async fn example(track: Track) {
+/// let client = MockSpotifyClient::new().with_track(track.clone());
+///
+/// let fetched = client.get_track(&track.id).await.unwrap();
+/// assert_eq!(fetched.name, track.name);
+/// assert!(matches!(client.get_track("unknown").await, Err(RustyError::NotFound(_))));
+/// # }
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct MockSpotifyClient {
+    /// The canned albums, keyed by id.
+    albums: HashMap<String, Album>,
+    /// The canned artists, keyed by id.
+    artists: HashMap<String, Artist>,
+    /// The canned tracks, keyed by id.
+    tracks: HashMap<String, Track>,
+    /// The canned playlists, keyed by id.
+    playlists: HashMap<String, Playlist>,
+    /// The response returned for any recommendations request.
+    recommendations: Option<RecommendationsResponse>,
+    /// The results returned for any search.
+    search_results: Option<SearchResults>,
+}
+
+impl MockSpotifyClient {
+    /// Creates a mock client without any canned data.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers an album, returned by `get_album` for its id.
+    pub fn with_album(mut self, album: Album) -> Self {
+        self.albums.insert(album.id.clone(), album);
+        self
+    }
+
+    /// Registers an artist, returned by `get_artist` for its id.
+    pub fn with_artist(mut self, artist: Artist) -> Self {
+        self.artists.insert(artist.id.clone(), artist);
+        self
+    }
+
+    /// Registers a track, returned by `get_track` for its id.
+    pub fn with_track(mut self, track: Track) -> Self {
+        self.tracks.insert(track.id.clone(), track);
+        self
+    }
+
+    /// Registers a playlist, returned by `get_playlist` for its id.
+    pub fn with_playlist(mut self, playlist: Playlist) -> Self {
+        self.playlists.insert(playlist.id.clone(), playlist);
+        self
+    }
+
+    /// Sets the response returned by `get_recommendations`, whatever the request.
+    pub fn with_recommendations(mut self, recommendations: RecommendationsResponse) -> Self {
+        self.recommendations = Some(recommendations);
+        self
+    }
+
+    /// Sets the results returned by `search`, whatever the query.
+    pub fn with_search_results(mut self, search_results: SearchResults) -> Self {
+        self.search_results = Some(search_results);
+        self
+    }
+}
+
+impl SpotifyApi for MockSpotifyClient {
+    /// Returns the canned album with the given id.
+    async fn get_album(&self, album_id: &str) -> RustyResult<Album> {
+        canned(&self.albums, "album", album_id)
+    }
+
+    /// Returns the canned artist with the given id.
+    async fn get_artist(&self, artist_id: &str) -> RustyResult<Artist> {
+        canned(&self.artists, "artist", artist_id)
+    }
+
+    /// Returns the canned track with the given id.
+    async fn get_track(&self, track_id: &str) -> RustyResult<Track> {
+        canned(&self.tracks, "track", track_id)
+    }
+
+    /// Returns the canned playlist with the given id.
+    async fn get_playlist(&self, playlist_id: &str) -> RustyResult<Playlist> {
+        canned(&self.playlists, "playlist", playlist_id)
+    }
+
+    /// Returns the canned recommendations, ignoring the request.
+    async fn get_recommendations(
+        &self,
+        _request: &RecommendationsRequest
+    ) -> RustyResult<RecommendationsResponse> {
+        self.recommendations
+            .clone()
+            .ok_or_else(|| {
+                RustyError::Unexpected("no recommendations registered in MockSpotifyClient".into())
+            })
+    }
+
+    /// Returns the canned search results, ignoring the query.
+    async fn search(
+        &self,
+        _query: &str,
+        _types: &[SearchType],
+        _market: Option<Market>,
+        _limit: Option<u32>,
+        _offset: Option<u32>
+    ) -> RustyResult<SearchResults> {
+        self.search_results
+            .clone()
+            .ok_or_else(|| {
+                RustyError::Unexpected("no search results registered in MockSpotifyClient".into())
+            })
+    }
+}
+
+/// Looks up a canned value, failing like a missing resource would.
+fn canned<T: Clone>(values: &HashMap<String, T>, kind: &str, id: &str) -> RustyResult<T> {
+    values
+        .get(id)
+        .cloned()
+        .ok_or_else(|| {
+            RustyError::NotFound(format!("no {kind} with id {id} registered in MockSpotifyClient"))
+        })
+}
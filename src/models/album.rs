@@ -1,8 +1,12 @@
+use std::fmt;
+
 use serde::{ Deserialize, Serialize };
 
 use super::{
-    artist::SimplifiedArtist,
-    data_change_fix::as_u32,
+    artist::{ artist_names, SimplifiedArtist },
+    Availability,
+    data_change_fix::{ as_u32, skip_nulls },
+    normalize_genres,
     page::Page,
     track::SimplifiedTrack,
     ExternalUrls,
@@ -37,7 +41,7 @@ pub struct Album {
 pub struct SimplifiedAlbum {
     pub album_type: String,
     pub total_tracks: i32,
-    pub available_markets: Vec<String>,
+    pub available_markets: Option<Vec<String>>,
     pub external_urls: ExternalUrls,
     pub href: String,
     pub id: String,
@@ -50,8 +54,84 @@ pub struct SimplifiedAlbum {
     pub artists: Vec<SimplifiedArtist>,
 }
 
+impl Album {
+    /// Returns where the album can be played, telling a missing `available_markets` apart from
+    /// an empty one.
+    pub fn availability(&self) -> Availability {
+        Availability::from_markets(self.available_markets.as_ref())
+    }
+
+    /// Returns the album's genres normalized for grouping, leaving `genres` untouched.
+    ///
+    /// Genres are lowercased, use single spaces instead of `-`/`_` separators, and are
+    /// deduplicated, so `"Hip-Hop"` and `"hip hop"` are counted once as `"hip hop"`.
+    pub fn normalized_genres(&self) -> Vec<String> {
+        normalize_genres(&self.genres)
+    }
+}
+
+impl SimplifiedAlbum {
+    /// Returns where the album can be played, telling a missing `available_markets` apart from
+    /// an empty one.
+    pub fn availability(&self) -> Availability {
+        Availability::from_markets(self.available_markets.as_ref())
+    }
+}
+
+/// Formats the album as `Artist - Name (id)`, joining several artists with commas.
+impl fmt::Display for Album {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} - {} ({})", artist_names(&self.artists), self.name, self.id)
+    }
+}
+
+/// Drops the album's `tracks`, `copyrights`, `genres`, `popularity` and `label`, keeping every
+/// other field.
+impl From<Album> for SimplifiedAlbum {
+    fn from(album: Album) -> Self {
+        SimplifiedAlbum {
+            album_type: album.album_type,
+            total_tracks: album.total_tracks,
+            available_markets: album.available_markets,
+            external_urls: album.external_urls,
+            href: album.href,
+            id: album.id,
+            images: album.images,
+            name: album.name,
+            release_date: album.release_date,
+            release_date_precision: album.release_date_precision,
+            r#type: album.r#type,
+            uri: album.uri,
+            artists: album.artists,
+        }
+    }
+}
+
+/// The relationship between an artist and an album, used to filter an artist's albums.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum AlbumGroup {
+    Album,
+    Single,
+    AppearsOn,
+    Compilation,
+}
+
+impl AlbumGroup {
+    /// Returns the value Spotify uses for the group in `include_groups`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlbumGroup::Album => "album",
+            AlbumGroup::Single => "single",
+            AlbumGroup::AppearsOn => "appears_on",
+            AlbumGroup::Compilation => "compilation",
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Albums {
+    #[serde(deserialize_with = "skip_nulls")]
     pub albums: Vec<Album>,
 }
 
@@ -1,6 +1,15 @@
+use std::fmt;
+
 use serde::{ Deserialize, Serialize };
 
-use super::{ data_change_fix::as_u32, ExternalUrls, Followers, SpotifyImage };
+use super::{
+    data_change_fix::{ as_u32, skip_nulls },
+    normalize_genres,
+    page::CursorBasedPage,
+    ExternalUrls,
+    Followers,
+    SpotifyImage,
+};
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Artist {
@@ -16,11 +25,35 @@ pub struct Artist {
     pub popularity: u32,
 }
 
+impl Artist {
+    /// Returns the artist's genres normalized for grouping, leaving `genres` untouched.
+    ///
+    /// Genres are lowercased, use single spaces instead of `-`/`_` separators, and are
+    /// deduplicated, so `"Hip-Hop"` and `"hip hop"` are counted once as `"hip hop"`.
+    pub fn normalized_genres(&self) -> Vec<String> {
+        normalize_genres(&self.genres)
+    }
+}
+
+/// Formats the artist as `Name (id)`.
+impl fmt::Display for Artist {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.name, self.id)
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Artists {
+    #[serde(deserialize_with = "skip_nulls")]
     pub artists: Vec<Artist>,
 }
 
+/// The artists a user follows, as returned by the followed artists endpoint.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct FollowedArtists {
+    pub artists: CursorBasedPage<Artist>,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct SimplifiedArtist {
     pub id: String,
@@ -28,3 +61,25 @@ pub struct SimplifiedArtist {
     pub external_urls: ExternalUrls,
     pub href: Option<String>,
 }
+
+/// Drops the artist's `images`, `followers`, `genres`, `type`, `uri` and `popularity`, keeping its
+/// `id`, `name` and `external_urls`. `href` is `None`, as full artist objects do not carry it.
+impl From<Artist> for SimplifiedArtist {
+    fn from(artist: Artist) -> Self {
+        SimplifiedArtist {
+            id: artist.id,
+            name: artist.name,
+            external_urls: artist.external_urls,
+            href: None,
+        }
+    }
+}
+
+/// Joins the names of `artists` with commas, e.g. `Daft Punk, Pharrell Williams`.
+pub(crate) fn artist_names(artists: &[SimplifiedArtist]) -> String {
+    artists
+        .iter()
+        .map(|artist| artist.name.as_str())
+        .collect::<Vec<&str>>()
+        .join(", ")
+}
@@ -0,0 +1,127 @@
+use serde::{ Deserialize, Serialize };
+
+/// The audio features of a track, as computed by Spotify.
+///
+/// The same attributes can be tuned in a [`RecommendationsRequest`](super::recommendations::RecommendationsRequest).
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AudioFeatures {
+    /// A confidence measure from 0.0 to 1.0 of whether the track is acoustic.
+    pub acousticness: f32,
+    /// How suitable the track is for dancing, from 0.0 (least danceable) to 1.0 (most danceable).
+    pub danceability: f32,
+    /// A perceptual measure of intensity and activity, from 0.0 to 1.0.
+    pub energy: f32,
+    /// Predicts whether the track contains no vocals, from 0.0 to 1.0.
+    pub instrumentalness: f32,
+    /// The key the track is in, in standard pitch class notation (0 = C, 1 = C♯/D♭, ...),
+    /// or -1 if no key was detected.
+    pub key: i32,
+    /// Detects the presence of an audience in the recording, from 0.0 to 1.0.
+    pub liveness: f32,
+    /// The overall loudness of the track in decibels, typically between -60 and 0.
+    pub loudness: f32,
+    /// The modality of the track: 1 for major, 0 for minor.
+    pub mode: i32,
+    /// Detects the presence of spoken words in the track, from 0.0 to 1.0.
+    pub speechiness: f32,
+    /// The overall estimated tempo of the track in beats per minute.
+    pub tempo: f32,
+    /// The estimated number of beats in each bar, from 3 to 7.
+    pub time_signature: i32,
+    /// The musical positiveness conveyed by the track, from 0.0 (sad) to 1.0 (happy).
+    pub valence: f32,
+    pub duration_ms: u64,
+    pub id: String,
+    pub uri: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AudioFeaturesResponse {
+    /// The features of each requested track, in request order, with `None` for the IDs Spotify
+    /// could not find.
+    pub audio_features: Vec<Option<AudioFeatures>>,
+}
+
+/// The low-level audio analysis of a track: its structure and musical content, such as rhythm,
+/// pitch and timbre.
+///
+/// Only the fields useful to applications are kept: the large fingerprint strings of the `track`
+/// object and the `meta` object are skipped while deserializing.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AudioAnalysis {
+    /// A summary of the whole track.
+    pub track: AudioAnalysisTrack,
+    /// The bars (or measures) of the track, each a group of beats.
+    pub bars: Vec<TimeInterval>,
+    /// The beats of the track, the basic time unit of a piece of music.
+    pub beats: Vec<TimeInterval>,
+    /// The sections of the track, such as the verse, chorus or bridge.
+    pub sections: Vec<Section>,
+    /// The segments of the track, each roughly consistent in sound, often under a second long.
+    pub segments: Vec<Segment>,
+    /// The tatums of the track, the lowest regular pulse train a listener infers.
+    pub tatums: Vec<TimeInterval>,
+}
+
+/// A summary of a track's audio analysis.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AudioAnalysisTrack {
+    pub num_samples: u64,
+    /// The length of the track in seconds.
+    pub duration: f64,
+    pub loudness: f32,
+    pub tempo: f32,
+    pub tempo_confidence: f32,
+    pub time_signature: i32,
+    pub time_signature_confidence: f32,
+    pub key: i32,
+    pub key_confidence: f32,
+    pub mode: i32,
+    pub mode_confidence: f32,
+}
+
+/// A span of a track, such as a bar, beat or tatum.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct TimeInterval {
+    /// The starting point of the interval, in seconds.
+    pub start: f64,
+    /// The duration of the interval, in seconds.
+    pub duration: f64,
+    /// The confidence, from 0.0 to 1.0, of the reliability of the interval.
+    pub confidence: f32,
+}
+
+/// A large variation in rhythm or timbre of a track, such as a chorus or verse.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Section {
+    pub start: f64,
+    pub duration: f64,
+    pub confidence: f32,
+    pub loudness: f32,
+    pub tempo: f32,
+    pub tempo_confidence: f32,
+    pub key: i32,
+    pub key_confidence: f32,
+    pub mode: i32,
+    pub mode_confidence: f32,
+    pub time_signature: i32,
+    pub time_signature_confidence: f32,
+}
+
+/// A short span of a track that is roughly consistent in sound.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Segment {
+    pub start: f64,
+    pub duration: f64,
+    pub confidence: f32,
+    pub loudness_start: f32,
+    pub loudness_max: f32,
+    pub loudness_max_time: f32,
+    /// Only set on the last segment of a track.
+    #[serde(default)]
+    pub loudness_end: Option<f32>,
+    /// The relative dominance of each of the 12 pitch classes, from 0.0 to 1.0.
+    pub pitches: Vec<f32>,
+    /// The 12 timbre coefficients of the segment, describing its sound quality.
+    pub timbre: Vec<f32>,
+}
@@ -0,0 +1,36 @@
+use serde::{ Deserialize, Serialize };
+
+use super::{ page::Page, SpotifyImage };
+
+/// A category used to tag items in Spotify, as shown on the browse tab.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Category {
+    pub href: String,
+    pub icons: Vec<SpotifyImage>,
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Categories {
+    pub categories: Page<Category>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_category_icon_without_dimensions() {
+        let category = serde_json::json!({
+            "href": "https://api.spotify.com/v1/browse/categories/toplists",
+            "icons": [{ "url": "https://t.scdn.co/media/derived/toplists.jpg" }],
+            "id": "toplists",
+            "name": "Top Lists"
+        });
+
+        let category: Category = serde_json::from_value(category).unwrap();
+        assert!(category.icons[0].width.is_none());
+        assert!(category.icons[0].height.is_none());
+    }
+}
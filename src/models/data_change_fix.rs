@@ -20,3 +20,13 @@ pub fn as_some_u32<'de, D>(deserializer: D) -> Result<Option<u32>, D::Error>
         None => Ok(None),
     }
 }
+
+/// Deserializes a list in which Spotify returns `null` for the ids it could not resolve,
+/// dropping the `null` entries.
+pub fn skip_nulls<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+    where D: Deserializer<'de>, T: Deserialize<'de>
+{
+    let items: Vec<Option<T>> = Deserialize::deserialize(deserializer)?;
+
+    Ok(items.into_iter().flatten().collect())
+}
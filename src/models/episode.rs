@@ -0,0 +1,29 @@
+use serde::{ Deserialize, Serialize };
+
+use super::{ data_change_fix::skip_nulls, ExternalUrls, SpotifyImage };
+
+/// A podcast episode.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Episode {
+    pub id: String,
+    pub name: String,
+    /// The plain text description of the episode.
+    pub description: String,
+    pub duration_ms: u64,
+    pub explicit: bool,
+    pub release_date: String,
+    pub release_date_precision: String,
+    pub images: Vec<SpotifyImage>,
+    /// A URL to a 30 second preview of the episode, if available.
+    pub audio_preview_url: Option<String>,
+    pub external_urls: ExternalUrls,
+    pub uri: String,
+    /// The languages used in the episode, as ISO 639-1 codes.
+    pub languages: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Episodes {
+    #[serde(deserialize_with = "skip_nulls")]
+    pub episodes: Vec<Episode>,
+}
@@ -0,0 +1,140 @@
+use std::{ fmt, str::FromStr };
+
+use serde::{ Deserialize, Deserializer, Serialize, Serializer };
+
+use crate::RustyError;
+
+/// The markets where Spotify is available, as returned by `/markets`.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct MarketsResponse {
+    /// ISO 3166-1 alpha-2 country codes, e.g. `"SE"`.
+    pub markets: Vec<String>,
+}
+
+/// Generates the [`Market`] enum with a variant per country code.
+macro_rules! markets {
+    ($($code:ident),* $(,)?) => {
+        /// A market, i.e. the country whose catalog a request is served from.
+        ///
+        /// The variants are the ISO 3166-1 alpha-2 country codes, plus [`Market::FromToken`] for the
+        /// market of the user a token was issued to. Parse user input with [`FromStr`], which
+        /// accepts the codes in any case and rejects unknown ones.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use rustyspoty::models::market::Market;
+        ///
+        /// let market: Market = "se".parse().unwrap();
+        /// assert_eq!(market, Market::SE);
+        /// assert_eq!(market.as_str(), "SE");
+        /// assert!("USA".parse::<Market>().is_err());
+        /// ```
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum Market {
+            /// The market of the user the access token was issued to. Requires a user access token.
+            FromToken,
+            $(
+                #[doc = concat!("The `", stringify!($code), "` market.")]
+                $code,
+            )*
+        }
+
+        impl Market {
+            /// Returns the value Spotify expects for the market: the uppercase country code, or
+            /// `from_token`.
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    Market::FromToken => "from_token",
+                    $(Market::$code => stringify!($code),)*
+                }
+            }
+        }
+
+        impl FromStr for Market {
+            type Err = RustyError;
+
+            /// Parses a country code, in any case, or `from_token`.
+            ///
+            /// # Errors
+            ///
+            /// Returns `RustyError::Io` with `InvalidInput` if `s` is not an ISO 3166-1 alpha-2
+            /// country code, e.g. `USA`.
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                if s.eq_ignore_ascii_case("from_token") {
+                    return Ok(Market::FromToken);
+                }
+                match s.to_ascii_uppercase().as_str() {
+                    $(stringify!($code) => Ok(Market::$code),)*
+                    _ =>
+                        Err(
+                            RustyError::invalid_input(
+                                &format!("{s} is not an ISO 3166-1 alpha-2 country code")
+                            )
+                        ),
+                }
+            }
+        }
+    };
+}
+
+markets! {
+    AD, AE, AF, AG, AI, AL, AM, AO, AQ, AR, AS, AT, AU, AW, AX, AZ, BA, BB, BD, BE, BF, BG, BH, BI,
+    BJ, BL, BM, BN, BO, BQ, BR, BS, BT, BV, BW, BY, BZ, CA, CC, CD, CF, CG, CH, CI, CK, CL, CM, CN,
+    CO, CR, CU, CV, CW, CX, CY, CZ, DE, DJ, DK, DM, DO, DZ, EC, EE, EG, EH, ER, ES, ET, FI, FJ, FK,
+    FM, FO, FR, GA, GB, GD, GE, GF, GG, GH, GI, GL, GM, GN, GP, GQ, GR, GS, GT, GU, GW, GY, HK, HM,
+    HN, HR, HT, HU, ID, IE, IL, IM, IN, IO, IQ, IR, IS, IT, JE, JM, JO, JP, KE, KG, KH, KI, KM, KN,
+    KP, KR, KW, KY, KZ, LA, LB, LC, LI, LK, LR, LS, LT, LU, LV, LY, MA, MC, MD, ME, MF, MG, MH, MK,
+    ML, MM, MN, MO, MP, MQ, MR, MS, MT, MU, MV, MW, MX, MY, MZ, NA, NC, NE, NF, NG, NI, NL, NO, NP,
+    NR, NU, NZ, OM, PA, PE, PF, PG, PH, PK, PL, PM, PN, PR, PS, PT, PW, PY, QA, RE, RO, RS, RU, RW,
+    SA, SB, SC, SD, SE, SG, SH, SI, SJ, SK, SL, SM, SN, SO, SR, SS, ST, SV, SX, SY, SZ, TC, TD, TF,
+    TG, TH, TJ, TK, TL, TM, TN, TO, TR, TT, TV, TW, TZ, UA, UG, UM, US, UY, UZ, VA, VC, VE, VG, VI,
+    VN, VU, WF, WS, XK, YE, YT, ZA, ZM, ZW
+}
+
+impl fmt::Display for Market {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<Market> for String {
+    fn from(market: Market) -> String {
+        market.as_str().to_string()
+    }
+}
+
+impl Serialize for Market {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Market {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        code.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_market_parses_codes_in_any_case() {
+        assert_eq!("us".parse::<Market>().unwrap(), Market::US);
+        assert_eq!("GB".parse::<Market>().unwrap(), Market::GB);
+        assert_eq!("From_Token".parse::<Market>().unwrap(), Market::FromToken);
+        assert!("USA".parse::<Market>().is_err());
+        assert!("".parse::<Market>().is_err());
+    }
+
+    #[test]
+    fn test_market_serializes_as_its_code() {
+        assert_eq!(Market::DE.to_string(), "DE");
+        assert_eq!(serde_json::to_value(Market::FromToken).unwrap(), "from_token");
+        let market: Market = serde_json::from_value(serde_json::json!("se")).unwrap();
+        assert_eq!(market, Market::SE);
+    }
+}
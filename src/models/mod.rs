@@ -14,16 +14,88 @@ pub mod playlist;
 pub mod track;
 pub mod user;
 pub mod auth;
+pub mod category;
+pub mod search;
+pub mod audio;
+pub mod episode;
+pub mod show;
+pub mod market;
+pub mod player;
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct SpotifyImage {
     pub url: String,
-    #[serde(deserialize_with = "as_some_u32")]
+    #[serde(default, deserialize_with = "as_some_u32")]
     pub height: Option<u32>,
-    #[serde(deserialize_with = "as_some_u32")]
+    #[serde(default, deserialize_with = "as_some_u32")]
     pub width: Option<u32>,
 }
 
+impl SpotifyImage {
+    /// Returns the width divided by the height, or `None` when either dimension is unknown
+    /// or the height is zero.
+    pub fn aspect_ratio(&self) -> Option<f32> {
+        match (self.width, self.height) {
+            (Some(width), Some(height)) if height > 0 => Some((width as f32) / (height as f32)),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of pixels of the image, or `None` when either dimension is unknown.
+    fn area(&self) -> Option<u64> {
+        Some((self.width? as u64) * (self.height? as u64))
+    }
+}
+
+/// Returns the image with the most pixels.
+///
+/// Images without known dimensions rank below all others. Ties, including a list where no
+/// image has dimensions, go to the earliest image, as Spotify lists images widest first.
+/// Returns `None` only when `images` is empty.
+pub fn largest_image(images: &[SpotifyImage]) -> Option<&SpotifyImage> {
+    images
+        .iter()
+        .enumerate()
+        .max_by_key(|(index, image)| (image.area(), std::cmp::Reverse(*index)))
+        .map(|(_, image)| image)
+}
+
+/// Returns the image whose width is closest to `width`.
+///
+/// Images without a known width rank below all others. Ties go to the earliest image.
+/// Returns `None` only when `images` is empty.
+pub fn closest_to_width(images: &[SpotifyImage], width: u32) -> Option<&SpotifyImage> {
+    images
+        .iter()
+        .min_by_key(|image| (image.width.is_none(), image.width.map(|w| w.abs_diff(width))))
+}
+
+/// Where a track or album can be played, derived from Spotify's `available_markets`.
+///
+/// Spotify leaves `available_markets` out (or `null`) when it does not provide the markets in
+/// a context, e.g. when the item was requested with a `market`, while an empty list means the
+/// item is playable nowhere.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Availability {
+    /// Spotify did not say where the item can be played.
+    Unknown,
+    /// The item cannot be played in any market.
+    Nowhere,
+    /// The item can be played in these ISO 3166-1 alpha-2 markets.
+    In(Vec<String>),
+}
+
+impl Availability {
+    /// Interprets an `available_markets` field.
+    pub(crate) fn from_markets(markets: Option<&Vec<String>>) -> Self {
+        match markets {
+            None => Availability::Unknown,
+            Some(markets) if markets.is_empty() => Availability::Nowhere,
+            Some(markets) => Availability::In(markets.clone()),
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Followers {
     // pub href: Option<String>,
@@ -35,8 +107,91 @@ pub struct ExternalUrls {
     pub spotify: String,
 }
 
+/// Identifiers of an item in catalogs other than Spotify's, useful to match it across services.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExternalIds {
+    /// The International Standard Recording Code.
+    pub isrc: Option<String>,
+    /// The International Article Number.
+    pub ean: Option<String>,
+    /// The Universal Product Code.
+    pub upc: Option<String>,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct SpotifyCopyright {
     pub text: String,
     pub r#type: String,
 }
+
+/// Normalizes free-form Spotify genres so that variants of the same genre compare equal.
+///
+/// Each genre is trimmed and lowercased, `-` and `_` are replaced with spaces, runs of
+/// whitespace are collapsed to a single space, and duplicates are removed, keeping the first
+/// occurrence. `"Hip-Hop"` and `"hip  hop"` both become `"hip hop"`.
+pub(crate) fn normalize_genres(genres: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(genres.len());
+    for genre in genres {
+        let genre = genre
+            .to_lowercase()
+            .replace(['-', '_'], " ")
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ");
+        if !genre.is_empty() && !normalized.contains(&genre) {
+            normalized.push(genre);
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_genres() {
+        let genres = ["Hip-Hop", "hip hop", " hip  hop ", "drum_and_bass", "K-Pop", ""].map(
+            String::from
+        );
+        assert_eq!(normalize_genres(&genres), vec!["hip hop", "drum and bass", "k pop"]);
+    }
+
+    #[test]
+    fn test_availability_distinguishes_missing_and_empty_markets() {
+        assert_eq!(Availability::from_markets(None), Availability::Unknown);
+        assert_eq!(Availability::from_markets(Some(&vec![])), Availability::Nowhere);
+        assert_eq!(
+            Availability::from_markets(Some(&vec!["US".to_string()])),
+            Availability::In(vec!["US".to_string()])
+        );
+    }
+
+    fn image(url: &str, width: Option<u32>, height: Option<u32>) -> SpotifyImage {
+        SpotifyImage { url: url.to_string(), width, height }
+    }
+
+    #[test]
+    fn test_image_helpers_rank_unknown_dimensions_last() {
+        let images = vec![
+            image("unknown", None, None),
+            image("small", Some(64), Some(64)),
+            image("large", Some(640), Some(640)),
+            image("medium", Some(300), Some(300))
+        ];
+
+        assert_eq!(images[0].aspect_ratio(), None);
+        assert_eq!(image("wide", Some(200), Some(100)).aspect_ratio(), Some(2.0));
+        assert_eq!(image("flat", Some(200), Some(0)).aspect_ratio(), None);
+
+        assert_eq!(largest_image(&images).unwrap().url, "large");
+        assert_eq!(closest_to_width(&images, 0).unwrap().url, "small");
+        assert_eq!(closest_to_width(&images, 320).unwrap().url, "medium");
+        assert_eq!(closest_to_width(&images, 5000).unwrap().url, "large");
+
+        let unknown = [image("first", None, None), image("second", None, None)];
+        assert_eq!(largest_image(&unknown).unwrap().url, "first");
+        assert_eq!(closest_to_width(&unknown, 300).unwrap().url, "first");
+        assert!(largest_image(&[]).is_none());
+    }
+}
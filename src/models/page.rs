@@ -12,6 +12,55 @@ pub struct Page<T> {
     pub total: u32,
 }
 
+impl<T> Page<T> {
+    /// Transforms the items of the page with `f`, keeping the paging metadata.
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            href: self.href,
+            items: self.items.into_iter().map(f).collect(),
+            limit: self.limit,
+            next: self.next,
+            offset: self.offset,
+            previous: self.previous,
+            total: self.total,
+        }
+    }
+
+    /// Returns an iterator over the items of the page.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.items.iter()
+    }
+
+    /// The number of items in the page, which is at most `limit` and usually less than `total`.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Whether the page has no items.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+impl<T> IntoIterator for Page<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    /// Consumes the page, yielding its items.
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Page<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+
 /// Cursor-based paging object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
 pub struct CursorBasedPage<T> {
@@ -23,8 +72,75 @@ pub struct CursorBasedPage<T> {
     pub total: Option<u32>,
 }
 
+impl<T> CursorBasedPage<T> {
+    /// Transforms the items of the page with `f`, keeping the paging metadata.
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> CursorBasedPage<U> {
+        CursorBasedPage {
+            href: self.href,
+            items: self.items.into_iter().map(f).collect(),
+            limit: self.limit,
+            next: self.next,
+            cursors: self.cursors,
+            total: self.total,
+        }
+    }
+}
+
 /// Cursor object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
 pub struct Cursor {
     pub after: Option<String>,
+    /// Only returned by endpoints that can also page backwards, such as recently played tracks.
+    pub before: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_keeps_paging_metadata() {
+        let page = Page {
+            href: "https://api.spotify.com/v1/albums/album1/tracks".to_string(),
+            items: vec![1, 2, 3],
+            limit: 3,
+            next: Some("https://api.spotify.com/v1/albums/album1/tracks?offset=3".to_string()),
+            offset: 0,
+            previous: None,
+            total: 10,
+        };
+
+        let mapped = page.clone().map(|n| n.to_string());
+        assert_eq!(mapped.items, ["1", "2", "3"]);
+        assert_eq!(mapped.href, page.href);
+        assert_eq!(mapped.next, page.next);
+        assert_eq!((mapped.limit, mapped.offset, mapped.total), (3, 0, 10));
+
+        let cursor_page = CursorBasedPage {
+            items: vec![1, 2],
+            cursors: Some(Cursor { after: Some("2".to_string()), before: None }),
+            ..Default::default()
+        };
+        let mapped = cursor_page.map(|n| n * 10);
+        assert_eq!(mapped.items, [10, 20]);
+        assert_eq!(mapped.cursors, Some(Cursor { after: Some("2".to_string()), before: None }));
+    }
+
+    #[test]
+    fn test_page_iterates_over_its_items() {
+        let page = Page { items: vec![1, 2, 3], total: 10, ..Default::default() };
+        assert_eq!(page.len(), 3);
+        assert!(!page.is_empty());
+        assert_eq!(page.iter().sum::<i32>(), 6);
+
+        let mut borrowed = Vec::new();
+        for item in &page {
+            borrowed.push(*item);
+        }
+        assert_eq!(borrowed, [1, 2, 3]);
+
+        let owned: Vec<i32> = page.into_iter().collect();
+        assert_eq!(owned, [1, 2, 3]);
+        assert!(Page::<i32>::default().is_empty());
+    }
 }
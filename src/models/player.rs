@@ -0,0 +1,171 @@
+use serde::{ Deserialize, Serialize };
+
+use super::{ episode::Episode, track::Track, ExternalUrls };
+
+/// A device the user can play on, such as a computer, phone or speaker.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Device {
+    /// The device ID, missing for some devices that cannot be controlled through the Web API.
+    pub id: Option<String>,
+    pub is_active: bool,
+    pub is_private_session: bool,
+    /// Whether the device refuses commands sent through the Web API.
+    pub is_restricted: bool,
+    pub name: String,
+    /// The kind of device, e.g. `"Computer"`, `"Smartphone"` or `"Speaker"`.
+    pub r#type: String,
+    /// The current volume, in percent.
+    pub volume_percent: Option<u32>,
+    #[serde(default)]
+    pub supports_volume: bool,
+}
+
+/// The album, artist, playlist or show a playback was started from.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct PlayContext {
+    /// The kind of context, e.g. `"album"`, `"artist"`, `"playlist"` or `"show"`.
+    pub r#type: String,
+    pub href: Option<String>,
+    pub external_urls: ExternalUrls,
+    pub uri: String,
+}
+
+/// The track or episode being played.
+///
+/// # Examples
+///
+/// Keeping only the music of mixed items, e.g. the items of a playlist holding podcast episodes:
+///
+/// ```
+/// use rustyspoty::models::{ player::PlayingItem, track::Track };
+///
+/// fn music_tracks(items: &[PlayingItem]) -> Vec<&Track> {
+///     items.iter().filter_map(PlayingItem::as_track).collect()
+/// }
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "lowercase")]
+#[allow(clippy::large_enum_variant)]
+pub enum PlayingItem {
+    Track(Track),
+    Episode(Episode),
+}
+
+impl PlayingItem {
+    /// Returns the track, or `None` if the item is an episode.
+    pub fn as_track(&self) -> Option<&Track> {
+        match self {
+            PlayingItem::Track(track) => Some(track),
+            PlayingItem::Episode(_) => None,
+        }
+    }
+
+    /// Returns the episode, or `None` if the item is a track.
+    pub fn as_episode(&self) -> Option<&Episode> {
+        match self {
+            PlayingItem::Track(_) => None,
+            PlayingItem::Episode(episode) => Some(episode),
+        }
+    }
+
+    /// Whether the item is a track.
+    pub fn is_track(&self) -> bool {
+        matches!(self, PlayingItem::Track(_))
+    }
+
+    /// Whether the item is an episode.
+    pub fn is_episode(&self) -> bool {
+        matches!(self, PlayingItem::Episode(_))
+    }
+}
+
+/// A track the user played, as returned by the recently played endpoint.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct PlayHistory {
+    pub track: Track,
+    /// When the track was played, as an ISO 8601 timestamp.
+    pub played_at: String,
+    /// Where the track was played from, if anywhere.
+    pub context: Option<PlayContext>,
+}
+
+/// The state of the user's playback, as returned by the `/me/player` endpoint.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CurrentlyPlaying {
+    /// The device the playback runs on.
+    pub device: Device,
+    /// `"off"`, `"track"` or `"context"`.
+    pub repeat_state: String,
+    pub shuffle_state: bool,
+    /// Where the playback was started from, if anywhere.
+    pub context: Option<PlayContext>,
+    /// The UNIX timestamp, in milliseconds, at which the state was last changed.
+    pub timestamp: u64,
+    /// The progress into the item, in milliseconds.
+    pub progress_ms: Option<u64>,
+    pub is_playing: bool,
+    /// The item being played, missing e.g. during an advertisement.
+    pub item: Option<PlayingItem>,
+    /// `"track"`, `"episode"`, `"ad"` or `"unknown"`.
+    pub currently_playing_type: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_playing_item_accessors() {
+        let items: Vec<PlayingItem> = serde_json::from_value(
+            serde_json::json!([
+                {
+                    "type": "track",
+                    "album": {
+                        "album_type": "album",
+                        "total_tracks": 1,
+                        "external_urls": { "spotify": "https://open.spotify.com/album/album1" },
+                        "href": "https://api.spotify.com/v1/albums/album1",
+                        "id": "album1",
+                        "images": [],
+                        "name": "Album Name",
+                        "release_date": "2024-01-01",
+                        "release_date_precision": "day",
+                        "type": "album",
+                        "uri": "spotify:album:album1",
+                        "artists": []
+                    },
+                    "id": "track1",
+                    "name": "Track Name",
+                    "artists": [],
+                    "duration_ms": 180000,
+                    "preview_url": null,
+                    "external_urls": { "spotify": "https://open.spotify.com/track/track1" }
+                },
+                {
+                    "type": "episode",
+                    "id": "episode1",
+                    "name": "Episode Name",
+                    "description": "",
+                    "duration_ms": 1800000,
+                    "explicit": false,
+                    "release_date": "2024-01-01",
+                    "release_date_precision": "day",
+                    "images": [],
+                    "audio_preview_url": null,
+                    "external_urls": { "spotify": "https://open.spotify.com/episode/episode1" },
+                    "uri": "spotify:episode:episode1",
+                    "languages": ["en"]
+                }
+            ])
+        ).unwrap();
+
+        assert!(items[0].is_track() && !items[0].is_episode());
+        assert_eq!(items[0].as_track().map(|track| track.id.as_str()), Some("track1"));
+        assert!(items[0].as_episode().is_none());
+        assert!(items[1].is_episode() && !items[1].is_track());
+        assert_eq!(items[1].as_episode().map(|episode| episode.id.as_str()), Some("episode1"));
+
+        let music: Vec<&Track> = items.iter().filter_map(PlayingItem::as_track).collect();
+        assert_eq!(music.len(), 1);
+    }
+}
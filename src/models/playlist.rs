@@ -1,6 +1,8 @@
+use std::fmt;
+
 use serde::{ Deserialize, Serialize };
 
-use super::{ ExternalUrls, Followers, SpotifyImage, user::User, track::Track };
+use super::{ page::Page, ExternalUrls, Followers, SpotifyImage, user::User, track::Track };
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Playlist {
@@ -14,6 +16,56 @@ pub struct Playlist {
     pub external_urls: ExternalUrls,
 }
 
+/// Formats the playlist as `Name by Owner (id)`, using the owner's ID when they have no display name.
+impl fmt::Display for Playlist {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let owner = self.owner.display_name.as_deref().unwrap_or(&self.owner.id);
+        write!(f, "{} by {} ({})", self.name, owner, self.id)
+    }
+}
+
+/// A playlist as listed in search results and playlist listings, without its tracks.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct SimplifiedPlaylist {
+    pub collaborative: bool,
+    pub description: Option<String>,
+    pub external_urls: ExternalUrls,
+    pub href: String,
+    pub id: String,
+    pub images: Option<Vec<SpotifyImage>>,
+    pub name: String,
+    pub owner: User,
+    pub public: Option<bool>,
+    pub snapshot_id: String,
+    pub tracks: PlaylistTracksRef,
+    pub r#type: String,
+    pub uri: String,
+}
+
+/// Where to fetch the tracks of a simplified playlist, and how many there are.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct PlaylistTracksRef {
+    pub href: String,
+    pub total: u32,
+}
+
+/// A page of editorial playlists, as returned by the featured and category playlists endpoints.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct FeaturedPlaylists {
+    /// The localized headline of the playlists, e.g. `"Monday morning music, coming right up!"`.
+    /// Empty when Spotify does not provide one.
+    #[serde(default)]
+    pub message: String,
+    pub playlists: Page<SimplifiedPlaylist>,
+}
+
+/// The version of a playlist after it was edited, as returned by the playlist items endpoints.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct SnapshotResponse {
+    /// Identifies the new version of the playlist.
+    pub snapshot_id: String,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct PlaylistTracks {
     pub items: Vec<PlaylistTrackItem>,
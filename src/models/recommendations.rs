@@ -1,7 +1,18 @@
+use std::fmt;
+
 use serde::{ Deserialize, Serialize };
 use serde_json::Value;
 
-use super::track::Track;
+use crate::{ parse_spotify_uri, RustyError, RustyResult };
+
+use super::{ market::Market, track::Track };
+
+/// The number of recommendations Spotify returns when no `limit` is given.
+pub(crate) const DEFAULT_RECOMMENDATIONS_LIMIT: u8 = 20;
+/// The most recommendations a single request may ask for.
+const MAX_RECOMMENDATIONS_LIMIT: u8 = 100;
+/// The most artist, genre and track seeds a recommendations request may have in total.
+pub(crate) const MAX_SEEDS: usize = 5;
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct RecommendationsRequest {
@@ -17,14 +28,15 @@ pub struct RecommendationsRequest {
     /// Example: limit=10
     #[serde(skip_serializing_if = "Option::is_none")]
     pub limit: Option<u8>,
-    /// An ISO 3166-1 alpha-2 country code.
+    /// The market to recommend tracks available in.
     ///
-    /// If a country code is specified, only content that is available in that market will be returned.
+    /// If a market is specified, only content that is available in that market will be returned.
+    /// Falls back to the client's default market when `None`.
     ///
     /// If a valid user access token is specified in the request header,
     /// the country associated with the user account will take priority over this parameter.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub market: Option<String>,
+    pub market: Option<Market>,
     /// A comma separated list of any genres in the set of available genre seeds.
     /// Up to 5 seed values may be provided in any combination of seed_artists, seed_tracks and seed_genres.
     ///
@@ -184,6 +196,89 @@ impl RecommendationsRequest {
         }
     }
 
+    /// Returns a [`RecommendationsRequestBuilder`] for setting the seeds and tunable attributes
+    /// fluently.
+    pub fn builder() -> RecommendationsRequestBuilder {
+        RecommendationsRequestBuilder::default()
+    }
+
+    /// The total number of artist, genre and track seeds.
+    pub fn seed_count(&self) -> usize {
+        self.seed_artists.as_ref().map_or(0, Vec::len) +
+            self.seed_genres.as_ref().map_or(0, Vec::len) +
+            self.seed_tracks.as_ref().map_or(0, Vec::len)
+    }
+
+    /// Adds a genre seed, one of the available genre seeds such as `"drum-and-bass"`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RustyError::Io` with `InvalidInput`, leaving the request unchanged, if the request
+    /// already has 5 seeds in total.
+    pub fn add_seed_genre(&mut self, genre: impl Into<String>) -> RustyResult<&mut Self> {
+        self.ensure_seed_capacity()?;
+        self.seed_genres.get_or_insert_with(Vec::new).push(genre.into());
+        Ok(self)
+    }
+
+    /// Adds a seed artist, by Spotify ID, URI or `open.spotify.com` URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RustyError::Io` with `InvalidInput`, leaving the request unchanged, if the request
+    /// already has 5 seeds in total.
+    pub fn add_seed_artist(&mut self, artist_id: impl Into<String>) -> RustyResult<&mut Self> {
+        self.ensure_seed_capacity()?;
+        self.seed_artists.get_or_insert_with(Vec::new).push(artist_id.into());
+        Ok(self)
+    }
+
+    /// Adds a seed track, by Spotify ID, URI or `open.spotify.com` URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RustyError::Io` with `InvalidInput`, leaving the request unchanged, if the request
+    /// already has 5 seeds in total.
+    pub fn add_seed_track(&mut self, track_id: impl Into<String>) -> RustyResult<&mut Self> {
+        self.ensure_seed_capacity()?;
+        self.seed_tracks.get_or_insert_with(Vec::new).push(track_id.into());
+        Ok(self)
+    }
+
+    /// Fails if another seed would exceed the maximum number of seeds.
+    fn ensure_seed_capacity(&self) -> RustyResult<()> {
+        if self.seed_count() >= MAX_SEEDS {
+            return Err(RustyError::invalid_input("No more than 5 seeds in total are allowed."));
+        }
+        Ok(())
+    }
+
+    /// Serializes the request like `to_json`, with its artist and track seeds reduced to bare
+    /// Spotify IDs.
+    ///
+    /// Seeds may be given as bare IDs, Spotify URIs or `open.spotify.com` URLs.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RustyError::Io` with `InvalidInput` if a seed is neither a bare ID nor a URI or
+    /// URL of the expected type, e.g. a track URI in `seed_artists`.
+    pub(crate) fn to_normalized_json(&self) -> RustyResult<Value> {
+        let mut json = self.to_json()?;
+        for (field, seeds, kind) in [
+            ("seed_artists", &self.seed_artists, "artist"),
+            ("seed_tracks", &self.seed_tracks, "track"),
+        ] {
+            if let Some(seeds) = seeds {
+                let ids = seeds
+                    .iter()
+                    .map(|seed| normalize_seed(seed, field, kind))
+                    .collect::<RustyResult<Vec<String>>>()?;
+                json[field] = Value::from(ids);
+            }
+        }
+        Ok(json)
+    }
+
     // Deserialize from JSON using serde
     pub fn from_json(json: &Value) -> Result<Self, serde_json::Error> {
         serde_json::from_value(json.clone())
@@ -195,11 +290,199 @@ impl RecommendationsRequest {
     }
 }
 
+/// Reduces a seed of the `field` list, given as a bare ID, URI or URL, to the bare ID of a `kind`
+/// resource.
+fn normalize_seed(seed: &str, field: &str, kind: &str) -> RustyResult<String> {
+    match parse_spotify_uri(seed) {
+        Some((seed_kind, id)) if seed_kind == kind => Ok(id),
+        Some((seed_kind, _)) => {
+            Err(RustyError::invalid_input(&format!("{field} only accepts {kind}s, got the {seed_kind} {seed}")))
+        }
+        None if !seed.is_empty() && seed.chars().all(|c| c.is_ascii_alphanumeric()) => {
+            Ok(seed.to_string())
+        }
+        None => {
+            Err(
+                RustyError::invalid_input(
+                    &format!("{field} only accepts Spotify IDs, URIs or URLs, got {seed}")
+                )
+            )
+        }
+    }
+}
+
+impl Default for RecommendationsRequest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A builder for a [`RecommendationsRequest`], setting its seeds and tunable attributes fluently.
+///
+/// # Examples
+///
+/// ```
+/// use rustyspoty::models::recommendations::RecommendationsRequest;
+///
+/// let request = RecommendationsRequest::builder()
+///     .seed_genres(["pop"])
+///     .target_energy(0.8)
+///     .limit(10)
+///     .build()
+///     .unwrap();
+/// assert_eq!(request.seed_count(), 1);
+/// ```
+#[derive(Debug, Default)]
+pub struct RecommendationsRequestBuilder {
+    /// The request being built.
+    request: RecommendationsRequest,
+}
+
+/// Generates a setter for each tunable attribute of the request.
+macro_rules! tunable_setters {
+    ($($field:ident: $ty:ty,)*) => {
+        $(
+            #[doc = concat!("Sets `", stringify!($field), "`.")]
+            pub fn $field(mut self, value: $ty) -> Self {
+                self.request.$field = Some(value);
+                self
+            }
+        )*
+    };
+}
+
+impl RecommendationsRequestBuilder {
+    /// Sets the genre seeds, from the available genre seeds such as `"drum-and-bass"`.
+    pub fn seed_genres(mut self, genres: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.request.seed_genres = Some(genres.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Sets the seed artists, by Spotify ID, URI or `open.spotify.com` URL.
+    pub fn seed_artists(mut self, artist_ids: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.request.seed_artists = Some(artist_ids.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Sets the seed tracks, by Spotify ID, URI or `open.spotify.com` URL.
+    pub fn seed_tracks(mut self, track_ids: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.request.seed_tracks = Some(track_ids.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Sets the target number of recommended tracks, from 1 to 100.
+    pub fn limit(mut self, limit: u8) -> Self {
+        self.request.limit = Some(limit);
+        self
+    }
+
+    /// Sets the market to recommend tracks available in.
+    pub fn market(mut self, market: Market) -> Self {
+        self.request.market = Some(market);
+        self
+    }
+
+    tunable_setters! {
+        min_acousticness: f32,
+        max_acousticness: f32,
+        target_acousticness: f32,
+        min_danceability: f32,
+        max_danceability: f32,
+        target_danceability: f32,
+        min_duration_ms: i32,
+        max_duration_ms: i32,
+        target_duration_ms: i32,
+        min_energy: f32,
+        max_energy: f32,
+        target_energy: f32,
+        min_instrumentalness: f32,
+        max_instrumentalness: f32,
+        target_instrumentalness: f32,
+        min_key: f32,
+        max_key: f32,
+        target_key: f32,
+        min_liveness: f32,
+        max_liveness: f32,
+        target_liveness: f32,
+        min_loudness: f32,
+        max_loudness: f32,
+        target_loudness: f32,
+        min_mode: f32,
+        max_mode: f32,
+        target_mode: f32,
+        min_popularity: u32,
+        max_popularity: u32,
+        target_popularity: u32,
+        min_speechiness: f32,
+        max_speechiness: f32,
+        target_speechiness: f32,
+        min_tempo: f32,
+        max_tempo: f32,
+        target_tempo: f32,
+        min_time_signature: f32,
+        max_time_signature: f32,
+        target_time_signature: f32,
+        min_valence: f32,
+        max_valence: f32,
+        target_valence: f32,
+    }
+
+    /// Creates the configured [`RecommendationsRequest`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `RustyError::Io` with `InvalidInput` unless the request has between 1 and 5 seeds
+    /// in total, or if its `limit` is outside 1-100.
+    pub fn build(self) -> RustyResult<RecommendationsRequest> {
+        let seed_count = self.request.seed_count();
+        if seed_count == 0 {
+            return Err(
+                RustyError::invalid_input("At least one seed (artist, genre, or track) is required.")
+            );
+        }
+        if seed_count > MAX_SEEDS {
+            return Err(RustyError::invalid_input("No more than 5 seeds in total are allowed."));
+        }
+        if let Some(limit) = self.request.limit {
+            if !(1..=MAX_RECOMMENDATIONS_LIMIT).contains(&limit) {
+                return Err(
+                    RustyError::invalid_input(
+                        &format!("limit must be between 1 and {MAX_RECOMMENDATIONS_LIMIT}, got {limit}")
+                    )
+                );
+            }
+        }
+        Ok(self.request)
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct GenreSeedsResponse {
     pub genres: Vec<String>,
 }
 
+/// The kind of entity a recommendation seed is.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum SeedType {
+    #[serde(alias = "artist")]
+    Artist,
+    #[serde(alias = "track")]
+    Track,
+    #[serde(alias = "genre")]
+    Genre,
+}
+
+impl fmt::Display for SeedType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SeedType::Artist => write!(f, "artist"),
+            SeedType::Track => write!(f, "track"),
+            SeedType::Genre => write!(f, "genre"),
+        }
+    }
+}
+
 #[allow(non_snake_case)]
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Seed {
@@ -208,11 +491,178 @@ pub struct Seed {
     pub href: Option<String>,
     pub id: String,
     pub initialPoolSize: i32,
-    pub r#type: String,
+    pub r#type: SeedType,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct RecommendationsResponse {
     pub seeds: Vec<Seed>,
     pub tracks: Vec<Track>,
+    /// The `limit` the recommendations were requested with, set by `get_recommendations`.
+    #[serde(skip)]
+    pub requested_limit: Option<u8>,
+}
+
+impl RecommendationsResponse {
+    /// The fraction of the requested `limit` that was actually returned, between 0.0 and 1.0.
+    ///
+    /// A value below 1.0 usually means the tunable attributes filtered out too much of the
+    /// seeds' track pools; see [`RecommendationsResponse::constrained_seeds`].
+    pub fn coverage(&self) -> f32 {
+        let limit = self.requested_limit.unwrap_or(DEFAULT_RECOMMENDATIONS_LIMIT).max(1);
+        ((self.tracks.len() as f32) / (limit as f32)).min(1.0)
+    }
+
+    /// Returns the seeds of the given type, e.g. the genre seeds, in the order Spotify returned them.
+    pub fn seeds_of_type(&self, seed_type: SeedType) -> Vec<&Seed> {
+        self.seeds
+            .iter()
+            .filter(|seed| seed.r#type == seed_type)
+            .collect()
+    }
+
+    /// Returns the seeds whose track pool shrank below the requested `limit` after filtering.
+    pub fn constrained_seeds(&self) -> Vec<&Seed> {
+        let limit = self.requested_limit.unwrap_or(DEFAULT_RECOMMENDATIONS_LIMIT) as i32;
+        self.seeds
+            .iter()
+            .filter(|seed| seed.afterFilteringSize < limit)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sixth_seed_is_rejected() {
+        let mut request = RecommendationsRequest::new();
+        request
+            .add_seed_genre("drum-and-bass").unwrap()
+            .add_seed_genre("r-n-b").unwrap()
+            .add_seed_artist("artist1").unwrap()
+            .add_seed_track("track1").unwrap()
+            .add_seed_track("track2").unwrap();
+        assert_eq!(request.seed_count(), 5);
+
+        assert!(matches!(request.add_seed_genre("pop"), Err(RustyError::Io(_))));
+        assert!(matches!(request.add_seed_artist("artist2"), Err(RustyError::Io(_))));
+        assert!(matches!(request.add_seed_track("track3"), Err(RustyError::Io(_))));
+        assert_eq!(request.seed_count(), 5);
+        assert_eq!(request.seed_genres, Some(vec!["drum-and-bass".to_string(), "r-n-b".to_string()]));
+    }
+
+    #[test]
+    fn test_seeds_are_normalized_to_bare_ids() {
+        let mut request = RecommendationsRequest::new();
+        request
+            .add_seed_artist("https://open.spotify.com/artist/4tZwfgrHOc3mvqYlEYSvVi?si=abc").unwrap()
+            .add_seed_track("spotify:track:4iV5W9uYEdYUVa79Axb7Rh").unwrap()
+            .add_seed_track("0c6xIDDpzE81m2q797ordA").unwrap()
+            .add_seed_genre("house").unwrap();
+
+        let json = request.to_normalized_json().unwrap();
+        assert_eq!(json["seed_artists"], serde_json::json!(["4tZwfgrHOc3mvqYlEYSvVi"]));
+        assert_eq!(
+            json["seed_tracks"],
+            serde_json::json!(["4iV5W9uYEdYUVa79Axb7Rh", "0c6xIDDpzE81m2q797ordA"])
+        );
+        assert_eq!(json["seed_genres"], serde_json::json!(["house"]));
+    }
+
+    #[test]
+    fn test_seed_of_the_wrong_type_is_rejected() {
+        let mut request = RecommendationsRequest::new();
+        request.add_seed_artist("spotify:track:4iV5W9uYEdYUVa79Axb7Rh").unwrap();
+
+        let result = request.to_normalized_json();
+        assert!(
+            matches!(result, Err(RustyError::Io(e)) if e.to_string().contains("seed_artists only accepts artists, got the track"))
+        );
+    }
+
+    #[test]
+    fn test_fractional_tunable_attributes_are_kept() {
+        let mut request = RecommendationsRequest::new();
+        request.min_speechiness = Some(0.25);
+        request.target_valence = Some(0.75);
+        request.target_tempo = Some(120.5);
+
+        let json = request.to_json().unwrap();
+        assert_eq!(json["min_speechiness"], serde_json::json!(0.25));
+        assert_eq!(json["target_valence"], serde_json::json!(0.75));
+        assert_eq!(json["target_tempo"], serde_json::json!(120.5));
+    }
+
+    #[test]
+    fn test_builder_sets_seeds_and_tunables() {
+        let request = RecommendationsRequest::builder()
+            .seed_genres(["pop"])
+            .seed_tracks(vec!["track1".to_string()])
+            .target_energy(0.8)
+            .min_popularity(50)
+            .limit(10)
+            .build()
+            .unwrap();
+        assert_eq!(request.seed_genres, Some(vec!["pop".to_string()]));
+        assert_eq!(request.seed_tracks, Some(vec!["track1".to_string()]));
+        assert_eq!(request.target_energy, Some(0.8));
+        assert_eq!(request.min_popularity, Some(50));
+        assert_eq!(request.limit, Some(10));
+    }
+
+    #[test]
+    fn test_builder_validates_seed_count() {
+        let result = RecommendationsRequest::builder().target_energy(0.8).build();
+        assert!(matches!(result, Err(RustyError::Io(_))));
+
+        let result = RecommendationsRequest::builder()
+            .seed_genres(["pop", "rock", "jazz"])
+            .seed_artists(["artist1", "artist2", "artist3"])
+            .build();
+        assert!(matches!(result, Err(RustyError::Io(_))));
+    }
+
+    #[test]
+    fn test_builder_validates_limit() {
+        for limit in [0, 101] {
+            let result = RecommendationsRequest::builder().seed_genres(["pop"]).limit(limit).build();
+            assert!(
+                matches!(result, Err(RustyError::Io(e)) if e.kind() == std::io::ErrorKind::InvalidInput)
+            );
+        }
+        for limit in [1, 100] {
+            assert!(RecommendationsRequest::builder().seed_genres(["pop"]).limit(limit).build().is_ok());
+        }
+    }
+
+    #[test]
+    fn test_seeds_of_type() {
+        let seed = |id: &str, seed_type: &str| {
+            serde_json::json!({
+                "afterFilteringSize": 100,
+                "afterRelinkingSize": 100,
+                "href": null,
+                "id": id,
+                "initialPoolSize": 100,
+                "type": seed_type
+            })
+        };
+        let json =
+            serde_json::json!({
+            "seeds": [seed("pop", "GENRE"), seed("artist1", "ARTIST"), seed("rock", "genre")],
+            "tracks": []
+        });
+        let response: RecommendationsResponse = serde_json::from_value(json).unwrap();
+
+        let genres: Vec<&str> = response
+            .seeds_of_type(SeedType::Genre)
+            .iter()
+            .map(|seed| seed.id.as_str())
+            .collect();
+        assert_eq!(genres, ["pop", "rock"]);
+        assert_eq!(response.seeds_of_type(SeedType::Artist).len(), 1);
+        assert!(response.seeds_of_type(SeedType::Track).is_empty());
+    }
 }
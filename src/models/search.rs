@@ -0,0 +1,65 @@
+use serde::{ Deserialize, Serialize };
+
+use super::{
+    album::SimplifiedAlbum,
+    artist::Artist,
+    page::Page,
+    playlist::SimplifiedPlaylist,
+    track::Track,
+};
+
+/// The kind of catalog item a search looks for.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchType {
+    Track,
+    Album,
+    Artist,
+    Playlist,
+}
+
+impl SearchType {
+    /// Returns the value Spotify uses for the type in the `type` parameter.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SearchType::Track => "track",
+            SearchType::Album => "album",
+            SearchType::Artist => "artist",
+            SearchType::Playlist => "playlist",
+        }
+    }
+}
+
+/// The results of a search, with one page per requested [`SearchType`].
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct SearchResults {
+    pub tracks: Option<Page<Track>>,
+    pub albums: Option<Page<SimplifiedAlbum>>,
+    pub artists: Option<Page<Artist>>,
+    pub playlists: Option<Page<SimplifiedPlaylist>>,
+}
+
+impl SearchResults {
+    /// Takes the page of `search_type` out of the results, wrapping its items in [`SearchItem`].
+    ///
+    /// Returns `None` if that type was not searched for.
+    pub fn into_page(self, search_type: SearchType) -> Option<Page<SearchItem>> {
+        match search_type {
+            SearchType::Track => self.tracks.map(|page| page.map(SearchItem::Track)),
+            SearchType::Album => self.albums.map(|page| page.map(SearchItem::Album)),
+            SearchType::Artist => self.artists.map(|page| page.map(SearchItem::Artist)),
+            SearchType::Playlist => self.playlists.map(|page| page.map(SearchItem::Playlist)),
+        }
+    }
+}
+
+/// A single search result, of any [`SearchType`].
+#[derive(Serialize, Debug, Clone)]
+#[serde(untagged)]
+#[allow(clippy::large_enum_variant)]
+pub enum SearchItem {
+    Track(Track),
+    Album(SimplifiedAlbum),
+    Artist(Artist),
+    Playlist(SimplifiedPlaylist),
+}
@@ -0,0 +1,23 @@
+use serde::{ Deserialize, Serialize };
+
+use super::{ ExternalUrls, SpotifyImage };
+
+/// A podcast show.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Show {
+    pub id: String,
+    pub name: String,
+    pub publisher: String,
+    /// The description of the show, as returned by Spotify. Depending on the show, it may
+    /// contain HTML markup or plain text.
+    pub description: String,
+    pub images: Vec<SpotifyImage>,
+    /// The media type of the show, e.g. `"audio"`.
+    pub media_type: String,
+    pub total_episodes: u32,
+    pub explicit: bool,
+    pub external_urls: ExternalUrls,
+    pub uri: String,
+    /// The languages used in the show, as ISO 639 codes.
+    pub languages: Vec<String>,
+}
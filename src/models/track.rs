@@ -1,6 +1,15 @@
+use std::{ fmt, time::Duration };
+
 use serde::{ Deserialize, Serialize };
 
-use super::{ album::SimplifiedAlbum, artist::SimplifiedArtist, ExternalUrls };
+use super::{
+    album::SimplifiedAlbum,
+    artist::{ artist_names, SimplifiedArtist },
+    data_change_fix::skip_nulls,
+    Availability,
+    ExternalIds,
+    ExternalUrls,
+};
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Track {
@@ -11,6 +20,76 @@ pub struct Track {
     pub duration_ms: u64,
     pub preview_url: Option<String>,
     pub external_urls: ExternalUrls,
+    #[serde(default)]
+    pub href: String,
+    #[serde(default)]
+    pub disc_number: u32,
+    #[serde(default)]
+    pub track_number: u32,
+    #[serde(default)]
+    pub explicit: bool,
+    /// ISO 3166-1 alpha-2 codes of the markets the track can be played in.
+    ///
+    /// Spotify omits this list when the track was requested with a `market`, since the
+    /// response is then already relinked for that market.
+    pub available_markets: Option<Vec<String>>,
+    /// Whether the track is playable in the requested market, only set when a market was given.
+    pub is_playable: Option<bool>,
+    /// The originally requested track, when Spotify relinked it to another track playable in
+    /// the requested market.
+    pub linked_from: Option<LinkedTrack>,
+    /// The popularity of the track, from 0 to 100, computed from its recent plays.
+    pub popularity: Option<u32>,
+    #[serde(default)]
+    pub uri: String,
+    /// The track's identifiers in other catalogs, such as its ISRC.
+    pub external_ids: Option<ExternalIds>,
+}
+
+impl Track {
+    /// Returns the length of the track.
+    pub fn duration(&self) -> Duration {
+        Duration::from_millis(self.duration_ms)
+    }
+
+    /// Returns the length of the track as `m:ss`, e.g. `3:07`.
+    pub fn duration_formatted(&self) -> String {
+        format_duration(self.duration_ms)
+    }
+
+    /// Returns where the track can be played, telling a missing `available_markets` apart from
+    /// an empty one.
+    pub fn availability(&self) -> Availability {
+        Availability::from_markets(self.available_markets.as_ref())
+    }
+
+    /// Returns `false` only when the track is known to be unplayable in `market`.
+    ///
+    /// Tracks without an `available_markets` list are treated as available, as Spotify gives no
+    /// information to rule them out.
+    pub fn is_available_in(&self, market: &str) -> bool {
+        match &self.available_markets {
+            Some(markets) => markets.iter().any(|m| m.eq_ignore_ascii_case(market)),
+            None => true,
+        }
+    }
+}
+
+/// Formats the track as `Artist - Name (id)`, joining several artists with commas.
+impl fmt::Display for Track {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} - {} ({})", artist_names(&self.artists), self.name, self.id)
+    }
+}
+
+/// A reference to the track a relinked track replaces.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct LinkedTrack {
+    pub external_urls: ExternalUrls,
+    pub href: String,
+    pub id: String,
+    pub r#type: String,
+    pub uri: String,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -25,9 +104,202 @@ pub struct SimplifiedTrack {
     pub name: String,
     pub preview_url: Option<String>,
     pub track_number: u32,
+    /// ISO 3166-1 alpha-2 codes of the markets the track can be played in, when provided.
+    pub available_markets: Option<Vec<String>>,
+    /// Whether the track is playable in the requested market, only set when a market was given.
+    pub is_playable: Option<bool>,
+    /// The originally requested track, when Spotify relinked it to another track playable in
+    /// the requested market.
+    pub linked_from: Option<LinkedTrack>,
+}
+
+impl SimplifiedTrack {
+    /// Returns the length of the track.
+    pub fn duration(&self) -> Duration {
+        Duration::from_millis(self.duration_ms)
+    }
+
+    /// Returns the length of the track as `m:ss`, e.g. `3:07`.
+    pub fn duration_formatted(&self) -> String {
+        format_duration(self.duration_ms)
+    }
+
+    /// Returns where the track can be played, telling a missing `available_markets` apart from
+    /// an empty one.
+    pub fn availability(&self) -> Availability {
+        Availability::from_markets(self.available_markets.as_ref())
+    }
+}
+
+/// Drops the track's `album`, keeping every other field.
+impl From<Track> for SimplifiedTrack {
+    fn from(track: Track) -> Self {
+        SimplifiedTrack {
+            artists: track.artists,
+            disc_number: track.disc_number,
+            duration_ms: track.duration_ms,
+            explicit: track.explicit,
+            external_urls: track.external_urls,
+            href: track.href,
+            id: track.id,
+            name: track.name,
+            preview_url: track.preview_url,
+            track_number: track.track_number,
+            available_markets: track.available_markets,
+            is_playable: track.is_playable,
+            linked_from: track.linked_from,
+        }
+    }
+}
+
+/// A track saved in the current user's library.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct SavedTrack {
+    /// When the track was saved, as an ISO 8601 timestamp, e.g. `2024-01-31T12:00:00Z`.
+    pub added_at: String,
+    pub track: Track,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct TracksResponse {
+    #[serde(deserialize_with = "skip_nulls")]
     pub tracks: Vec<Track>,
 }
+
+impl TracksResponse {
+    /// Drops the tracks that are not playable in `market`, keeping the order of the rest.
+    ///
+    /// Unlike the `market` query parameter, which asks Spotify to relink tracks to a playable
+    /// version, this only filters the tracks already returned. See [`Track::is_available_in`].
+    pub fn only_available_in(mut self, market: &str) -> Self {
+        self.tracks.retain(|track| track.is_available_in(market));
+        self
+    }
+}
+
+/// Formats `duration_ms` as `m:ss`, rounding down to the second.
+fn format_duration(duration_ms: u64) -> String {
+    let seconds = duration_ms / 1000;
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_track_into_simplified_track() {
+        let track = serde_json::json!({
+            "album": {
+                "album_type": "album",
+                "total_tracks": 12,
+                "available_markets": null,
+                "external_urls": { "spotify": "https://open.spotify.com/album/album1" },
+                "href": "https://api.spotify.com/v1/albums/album1",
+                "id": "album1",
+                "images": [],
+                "name": "Album Name",
+                "release_date": "2024-01-01",
+                "release_date_precision": "day",
+                "type": "album",
+                "uri": "spotify:album:album1",
+                "artists": []
+            },
+            "id": "track1",
+            "name": "Track Name",
+            "artists": [],
+            "duration_ms": 180000,
+            "preview_url": null,
+            "external_urls": { "spotify": "https://open.spotify.com/track/track1" },
+            "href": "https://api.spotify.com/v1/tracks/track1",
+            "disc_number": 2,
+            "track_number": 7,
+            "explicit": true
+        });
+        let track: Track = serde_json::from_value(track).unwrap();
+        assert_eq!(track.duration(), Duration::from_secs(180));
+
+        let simplified = SimplifiedTrack::from(track);
+        assert_eq!(simplified.id, "track1");
+        assert_eq!(simplified.duration_formatted(), "3:00");
+        assert_eq!(simplified.href, "https://api.spotify.com/v1/tracks/track1");
+        assert_eq!((simplified.disc_number, simplified.track_number), (2, 7));
+        assert!(simplified.explicit);
+    }
+
+    #[test]
+    fn test_track_external_ids() {
+        let track = serde_json::json!({
+            "album": {
+                "album_type": "album",
+                "total_tracks": 1,
+                "external_urls": { "spotify": "https://open.spotify.com/album/album1" },
+                "href": "https://api.spotify.com/v1/albums/album1",
+                "id": "album1",
+                "images": [],
+                "name": "Album Name",
+                "release_date": "2024-01-01",
+                "release_date_precision": "day",
+                "type": "album",
+                "uri": "spotify:album:album1",
+                "artists": []
+            },
+            "id": "track1",
+            "name": "Track Name",
+            "artists": [],
+            "duration_ms": 180000,
+            "preview_url": null,
+            "external_urls": { "spotify": "https://open.spotify.com/track/track1" },
+            "popularity": 73,
+            "uri": "spotify:track:track1",
+            "external_ids": { "isrc": "USUM71703861" }
+        });
+        let track: Track = serde_json::from_value(track).unwrap();
+
+        assert_eq!(track.popularity, Some(73));
+        assert_eq!(track.uri, "spotify:track:track1");
+        let external_ids = track.external_ids.unwrap();
+        assert_eq!(external_ids.isrc.as_deref(), Some("USUM71703861"));
+        assert_eq!(external_ids.upc, None);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(0), "0:00");
+        assert_eq!(format_duration(187_999), "3:07");
+        assert_eq!(format_duration(4_503_000), "75:03");
+    }
+
+    #[test]
+    fn test_track_display() {
+        let track = serde_json::json!({
+            "album": {
+                "album_type": "album",
+                "total_tracks": 1,
+                "available_markets": null,
+                "external_urls": { "spotify": "https://open.spotify.com/album/album1" },
+                "href": "https://api.spotify.com/v1/albums/album1",
+                "id": "album1",
+                "images": [],
+                "name": "Album Name",
+                "release_date": "2024-01-01",
+                "release_date_precision": "day",
+                "type": "album",
+                "uri": "spotify:album:album1",
+                "artists": []
+            },
+            "id": "track1",
+            "name": "Get Lucky",
+            "artists": [
+                { "id": "artist1", "name": "Daft Punk", "external_urls": { "spotify": "" }, "href": null },
+                { "id": "artist2", "name": "Pharrell Williams", "external_urls": { "spotify": "" }, "href": null }
+            ],
+            "duration_ms": 369626,
+            "preview_url": null,
+            "external_urls": { "spotify": "https://open.spotify.com/track/track1" }
+        });
+        let track: Track = serde_json::from_value(track).unwrap();
+
+        assert_eq!(track.to_string(), "Daft Punk, Pharrell Williams - Get Lucky (track1)");
+    }
+}
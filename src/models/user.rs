@@ -1,11 +1,147 @@
 use serde::{ Deserialize, Serialize };
 
-use super::ExternalUrls;
+use super::{ ExternalUrls, Followers, SpotifyImage };
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct User {
     pub id: String,
     pub display_name: Option<String>,
-    pub external_urls: ExternalUrls,
+    /// Missing on some owners of editorial playlists, such as the `spotify` user.
+    pub external_urls: Option<ExternalUrls>,
     pub r#type: String,
+    /// Only returned with the public profile of a user, see `get_user`.
+    pub followers: Option<Followers>,
+    /// Only returned with the public profile of a user, see `get_user`.
+    pub images: Option<Vec<SpotifyImage>>,
+}
+
+/// The profile of the current user, including the private fields only returned to the user
+/// themselves, see `SpotifyUserClient::get_current_user`.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct PrivateUser {
+    pub id: String,
+    pub display_name: Option<String>,
+    /// Only returned with the `user-read-email` scope.
+    pub email: Option<String>,
+    /// The ISO 3166-1 alpha-2 country of the account. Only returned with the
+    /// `user-read-private` scope.
+    pub country: Option<String>,
+    /// The subscription level, e.g. `premium` or `free`. Only returned with the
+    /// `user-read-private` scope.
+    pub product: Option<String>,
+    /// The explicit content settings. Only returned with the `user-read-private` scope.
+    pub explicit_content: Option<ExplicitContent>,
+    pub followers: Option<Followers>,
+    #[serde(default)]
+    pub images: Vec<SpotifyImage>,
+    pub external_urls: ExternalUrls,
+    pub uri: String,
+}
+
+/// The explicit content settings of a user.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ExplicitContent {
+    /// Whether explicit content should not be played.
+    pub filter_enabled: bool,
+    /// Whether the setting is locked and cannot be changed by the user.
+    pub filter_locked: bool,
+}
+
+/// The period a user's top items are computed over.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeRange {
+    /// Roughly the last 4 weeks.
+    ShortTerm,
+    /// Roughly the last 6 months.
+    #[default]
+    MediumTerm,
+    /// Roughly the last year.
+    LongTerm,
+}
+
+impl TimeRange {
+    /// Returns the value Spotify uses for the range in `time_range`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimeRange::ShortTerm => "short_term",
+            TimeRange::MediumTerm => "medium_term",
+            TimeRange::LongTerm => "long_term",
+        }
+    }
+}
+
+/// The kind of account a user can follow.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum FollowType {
+    Artist,
+    User,
+}
+
+impl FollowType {
+    /// Returns the value Spotify uses for the kind in `type`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FollowType::Artist => "artist",
+            FollowType::User => "user",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_editorial_playlist_owner() {
+        // Owner object of an editorial playlist, as returned by `/playlists/37i9dQZF1DX...`.
+        let owner = serde_json::json!({
+            "href": "https://api.spotify.com/v1/users/spotify",
+            "id": "spotify",
+            "type": "user",
+            "uri": "spotify:user:spotify"
+        });
+
+        let user: User = serde_json::from_value(owner).unwrap();
+        assert_eq!(user.id, "spotify");
+        assert!(user.display_name.is_none());
+        assert!(user.external_urls.is_none());
+    }
+
+    #[test]
+    fn test_public_user_profile() {
+        let profile = serde_json::json!({
+            "display_name": "Lilla Namo",
+            "external_urls": { "spotify": "https://open.spotify.com/user/smedjan" },
+            "followers": { "href": null, "total": 4561 },
+            "href": "https://api.spotify.com/v1/users/smedjan",
+            "id": "smedjan",
+            "images": [{ "url": "https://i.scdn.co/image/ab6775700000ee85", "height": 300, "width": 300 }],
+            "type": "user",
+            "uri": "spotify:user:smedjan"
+        });
+
+        let user: User = serde_json::from_value(profile).unwrap();
+        assert_eq!(user.followers.map(|followers| followers.total), Some(4561));
+        assert_eq!(user.images.map(|images| images.len()), Some(1));
+    }
+
+    #[test]
+    fn test_private_user_profile_without_private_scopes() {
+        let profile = serde_json::json!({
+            "display_name": "Lilla Namo",
+            "external_urls": { "spotify": "https://open.spotify.com/user/smedjan" },
+            "followers": { "href": null, "total": 4561 },
+            "href": "https://api.spotify.com/v1/users/smedjan",
+            "id": "smedjan",
+            "images": [],
+            "type": "user",
+            "uri": "spotify:user:smedjan"
+        });
+
+        let user: PrivateUser = serde_json::from_value(profile).unwrap();
+        assert!(user.email.is_none());
+        assert!(user.explicit_content.is_none());
+    }
 }
@@ -0,0 +1,129 @@
+use std::{ future::Future, pin::Pin, time::{ Duration, Instant } };
+
+use tokio::sync::Mutex as AsyncMutex;
+
+/// Throttles the requests a client sends to the Spotify Web API.
+///
+/// The client calls [`RateLimiter::acquire`] before every request that is not served from the
+/// cache, and only sends the request once the returned future completes. Implementations decide
+/// how long to wait, which makes the trait the hook for coordinating several processes through a
+/// shared store (e.g. Redis), while [`InProcessRateLimiter`] covers a single process.
+///
+/// With the `governor` feature enabled, `governor::DefaultDirectRateLimiter` implements this
+/// trait as well.
+///
+/// # Examples
+///
+/// Implementing a limiter backed by a shared store:
+///
+/// ```
+/// use std::{ future::Future, pin::Pin };
+/// use rustyspoty::RateLimiter;
+///
+/// struct SharedLimiter {
+///     // e.g. a Redis connection pool
+/// }
+///
+/// impl RateLimiter for SharedLimiter {
+///     fn acquire(&self) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+///         Box::pin(async move {
+///             // Reserve a slot in the shared store, sleeping until one is available.
+///         })
+///     }
+/// }
+/// ```
+pub trait RateLimiter: Send + Sync {
+    /// Waits until the next request may be sent.
+    ///
+    /// # Returns
+    ///
+    /// A boxed future that completes once a request slot has been reserved for the caller. The
+    /// future borrows the limiter and must be `Send`, as it is awaited inside the client's
+    /// request futures.
+    fn acquire(&self) -> Pin<Box<dyn Future<Output = ()> + Send + '_>>;
+}
+
+/// The state of an [`InProcessRateLimiter`]'s token bucket.
+struct Bucket {
+    /// The number of requests that may currently be sent without waiting.
+    tokens: f64,
+    /// The `Instant` the tokens were last replenished.
+    refilled_at: Instant,
+}
+
+/// A token-bucket [`RateLimiter`] shared by the clients of a single process.
+///
+/// Allows bursts of up to `max_requests` requests, refilled evenly over `period`.
+pub struct InProcessRateLimiter {
+    /// The size of the bucket, i.e. the largest burst allowed.
+    max_requests: f64,
+    /// How long it takes to refill the bucket from empty.
+    period: Duration,
+    /// The current token bucket, locked while a caller waits for its slot.
+    bucket: AsyncMutex<Bucket>,
+}
+
+impl InProcessRateLimiter {
+    /// Creates a limiter allowing `max_requests` requests per `period`.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_requests` - The number of requests allowed per `period`, at least 1.
+    /// * `period` - The window the requests are spread over.
+    pub fn new(max_requests: u32, period: Duration) -> Self {
+        let max_requests = max_requests.max(1) as f64;
+        InProcessRateLimiter {
+            max_requests,
+            period,
+            bucket: AsyncMutex::new(Bucket { tokens: max_requests, refilled_at: Instant::now() }),
+        }
+    }
+}
+
+impl RateLimiter for InProcessRateLimiter {
+    fn acquire(&self) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        Box::pin(async move {
+            // Holding the lock while sleeping makes waiters take their turns in order
+            let mut bucket = self.bucket.lock().await;
+            let refill_rate = self.max_requests / self.period.as_secs_f64();
+
+            let now = Instant::now();
+            let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+            bucket.tokens = (bucket.tokens + elapsed * refill_rate).min(self.max_requests);
+            bucket.refilled_at = now;
+
+            if bucket.tokens < 1.0 {
+                let wait = Duration::from_secs_f64((1.0 - bucket.tokens) / refill_rate);
+                tokio::time::sleep(wait).await;
+                bucket.tokens = 1.0;
+                bucket.refilled_at = Instant::now();
+            }
+            bucket.tokens -= 1.0;
+        })
+    }
+}
+
+#[cfg(feature = "governor")]
+impl RateLimiter for governor::DefaultDirectRateLimiter {
+    fn acquire(&self) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        Box::pin(self.until_ready())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_in_process_rate_limiter_spaces_requests() {
+        let limiter = InProcessRateLimiter::new(2, Duration::from_millis(200));
+        let start = Instant::now();
+
+        // The first two requests fit in the burst, the third waits for a refill
+        limiter.acquire().await;
+        limiter.acquire().await;
+        assert!(start.elapsed() < Duration::from_millis(50));
+        limiter.acquire().await;
+        assert!(start.elapsed() >= Duration::from_millis(90));
+    }
+}
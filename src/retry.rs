@@ -0,0 +1,79 @@
+use std::time::Duration;
+
+use rand::Rng;
+use reqwest::StatusCode;
+
+/// How a client retries requests failing with a transient server error.
+///
+/// Responses with status `500`, `502`, `503` or `504`, typically returned by Spotify's edge during
+/// brief outages, are retried up to `max_retries` times. The wait before each retry doubles, starting
+/// from `base_delay`, and a random jitter of up to the same amount is added so that clients failing
+/// together do not retry together. Other errors, such as `400`, `401` or `404`, are never retried.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use rustyspoty::{ RetryPolicy, SpotifyClientCredentials };
+///
+/// let client = SpotifyClientCredentials::builder(
+///     "client_id".to_string(),
+///     "client_secret".to_string()
+/// )
+///     .with_retry_policy(RetryPolicy {
+///         max_retries: 3,
+///         base_delay: Duration::from_millis(200),
+///     })
+///     .build();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The most times a failed request is retried.
+    pub max_retries: u32,
+    /// The wait before the first retry, doubled for every following one.
+    pub base_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Whether a response with `status` is worth retrying.
+    pub(crate) fn is_retryable(status: StatusCode) -> bool {
+        matches!(
+            status,
+            StatusCode::INTERNAL_SERVER_ERROR |
+                StatusCode::BAD_GATEWAY |
+                StatusCode::SERVICE_UNAVAILABLE |
+                StatusCode::GATEWAY_TIMEOUT
+        )
+    }
+
+    /// The wait before retry number `retry`, counted from 0: the exponential backoff plus jitter.
+    pub(crate) fn delay(&self, retry: u32) -> Duration {
+        let backoff = self.base_delay.saturating_mul(2u32.saturating_pow(retry));
+        let jitter = rand::thread_rng().gen_range(0.0..=1.0);
+        backoff.saturating_add(backoff.mul_f64(jitter))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay_backs_off_exponentially_with_jitter() {
+        let policy = RetryPolicy { max_retries: 3, base_delay: Duration::from_millis(100) };
+        for (retry, backoff) in [(0, 100), (1, 200), (2, 400)] {
+            let delay = policy.delay(retry);
+            assert!(delay >= Duration::from_millis(backoff));
+            assert!(delay <= Duration::from_millis(2 * backoff));
+        }
+    }
+
+    #[test]
+    fn test_only_transient_server_errors_are_retryable() {
+        assert!(RetryPolicy::is_retryable(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(RetryPolicy::is_retryable(StatusCode::BAD_GATEWAY));
+        assert!(!RetryPolicy::is_retryable(StatusCode::NOT_FOUND));
+        assert!(!RetryPolicy::is_retryable(StatusCode::UNAUTHORIZED));
+        assert!(!RetryPolicy::is_retryable(StatusCode::NOT_IMPLEMENTED));
+    }
+}
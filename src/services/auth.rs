@@ -1,11 +1,14 @@
 // src/queries/auth.rs
 
-use crate::models::auth::{ ClientCredsAuthResponse, ClientCredsAuthRequest };
+use crate::{ models::auth::{ ClientCredsAuthResponse, ClientCredsAuthRequest }, RustyError, RustyResult };
 
-pub async fn get_spotify_token(
-    client_id: &str,
-    client_secret: &str
-) -> Result<String, reqwest::Error> {
+/// Requests an access token with the client credentials flow.
+///
+/// # Errors
+///
+/// Returns `RustyError::TokenAuthentication` with the response body if Spotify rejects the
+/// credentials, or the `RustyError` of the underlying network failure.
+pub async fn get_spotify_token(client_id: &str, client_secret: &str) -> RustyResult<String> {
     let client = reqwest::Client::new();
     let params = ClientCredsAuthRequest {
         grant_type: "client_credentials".to_string(),
@@ -13,11 +16,14 @@ pub async fn get_spotify_token(
         client_secret: client_secret.to_string(),
     };
 
-    let res = client
-        .post("https://accounts.spotify.com/api/token")
-        .form(&params)
-        .send().await?
-        .json::<ClientCredsAuthResponse>().await?;
+    let response = client.post("https://accounts.spotify.com/api/token").form(&params).send().await?;
+    if !response.status().is_success() {
+        let error_message = response
+            .text().await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(RustyError::TokenAuthentication(error_message));
+    }
+    let res = response.json::<ClientCredsAuthResponse>().await?;
 
     Ok(res.access_token)
 }
@@ -1,12 +1,10 @@
 // src/queries/mod.rs
 
-use std::env;
-
 use regex::Regex;
-use reqwest;
+use reqwest::{ redirect::Policy, Response, StatusCode };
 use serde::de::DeserializeOwned;
 
-use self::auth::get_spotify_token;
+use crate::{ client_creds::env_var, error::api_error, RustyError, RustyResult };
 
 mod auth;
 mod track;
@@ -23,48 +21,126 @@ pub use self::{ auth::*, track::*, playlist::* };
 /// * `url` - The full URL to which the request will be sent.
 ///
 /// # Returns
-/// A `Result` containing either the deserialized response object or an error if the request fails.
+/// A `RustyResult` containing either the deserialized response object or an error if the request fails.
 ///
 /// # Errors
-/// Returns `reqwest::Error` if the request fails or if deserialization fails.
-pub async fn get_spotify_data<T>(url: &str) -> Result<T, reqwest::Error> where T: DeserializeOwned {
-    let client_id = env::var("SPOTIFY_CLIENT_ID").expect("Expected a client id");
-    let client_secret = env::var("SPOTIFY_CLIENT_SECRET").expect("Expected a client secret");
+/// * Returns `RustyError::Io` with `InvalidInput` if `SPOTIFY_CLIENT_ID` or `SPOTIFY_CLIENT_SECRET`
+///   is not set.
+/// * Returns `RustyError::SpotifyRateLimited` if Spotify answers `429 Too Many Requests`.
+/// * Returns `RustyError::Unexpected` if Spotify answers with any other error status.
+/// * Returns a network error such as `RustyError::Connect`, or `RustyError::ParseJson`, if the
+///   request or deserialization fails.
+pub async fn get_spotify_data<T>(url: &str) -> RustyResult<T> where T: DeserializeOwned {
+    let client_id = env_var("SPOTIFY_CLIENT_ID")?;
+    let client_secret = env_var("SPOTIFY_CLIENT_SECRET")?;
 
     let token = get_spotify_token(&client_id, &client_secret).await?;
     let client = reqwest::Client::new();
-    let res = client
+    let response = client
         .get(url)
         .header("Authorization", format!("Bearer {}", token))
-        .send().await?
-        .json::<T>().await?;
+        .send().await?;
+
+    parse_spotify_response(response).await
+}
 
-    Ok(res)
+/// Checks the status of a Spotify API response before deserializing its body.
+async fn parse_spotify_response<T>(response: Response) -> RustyResult<T> where T: DeserializeOwned {
+    match response.status() {
+        status if status.is_success() => Ok(serde_json::from_slice(&response.bytes().await?)?),
+        StatusCode::TOO_MANY_REQUESTS => {
+            let retry_after = response
+                .headers()
+                .get("Retry-After")
+                .and_then(|h| h.to_str().ok())
+                .and_then(|s| s.parse::<u64>().ok());
+            match retry_after {
+                Some(retry_after) => Err(RustyError::SpotifyRateLimited(retry_after)),
+                None =>
+                    Err(
+                        RustyError::Unexpected(
+                            "Rate limited by Spotify Web API, but no retry time provided.".into()
+                        )
+                    ),
+            }
+        }
+        status => {
+            let body = response.text().await.unwrap_or_default();
+            Err(api_error(status, &body))
+        }
+    }
 }
 
+/// The number of redirects `get_final_spotify_url` follows before giving up.
+pub const DEFAULT_MAX_REDIRECTS: usize = 10;
+
 /// Resolves the final URL from a shortened Spotify URL.
 ///
-/// This function performs an HTTP GET request to the shortened URL and returns the final URL after redirection.
+/// This function performs an HTTP GET request to the shortened URL and returns the final URL after redirection,
+/// following up to `DEFAULT_MAX_REDIRECTS` redirects.
+///
+/// # Arguments
+/// * `short_url` - The shortened URL to resolve.
+///
+/// # Returns
+/// A `RustyResult` containing either the final URL as a `String` or an error if the request fails.
+///
+/// # Errors
+/// See [`get_final_spotify_url_with_max_redirects`].
+pub async fn get_final_spotify_url(short_url: &str) -> RustyResult<String> {
+    get_final_spotify_url_with_max_redirects(short_url, DEFAULT_MAX_REDIRECTS).await
+}
+
+/// Resolves the final URL from a shortened Spotify URL, following at most `max_redirects` redirects.
 ///
 /// # Arguments
 /// * `short_url` - The shortened URL to resolve.
+/// * `max_redirects` - The most redirects to follow, guarding against redirect loops.
 ///
 /// # Returns
-/// A `Result` containing either the final URL as a `String` or an error if the request fails.
+/// A `RustyResult` containing either the final URL as a `String` or an error if the request fails.
 ///
 /// # Errors
-/// Returns `reqwest::Error` if the HTTP request fails.
-pub async fn get_final_spotify_url(short_url: &str) -> Result<String, reqwest::Error> {
-    let resp = reqwest::get(short_url).await?;
-    Ok(resp.url().to_string())
+/// * Returns `RustyError::Unexpected` if resolving the link takes more than `max_redirects` redirects,
+///   or if it does not resolve to an `open.spotify.com` URL.
+/// * Returns a network error such as `RustyError::Connect` if the HTTP request fails.
+pub async fn get_final_spotify_url_with_max_redirects(
+    short_url: &str,
+    max_redirects: usize
+) -> RustyResult<String> {
+    let client = reqwest::Client
+        ::builder()
+        .redirect(Policy::limited(max_redirects))
+        .build()?;
+    let resp = client.get(short_url).send().await.map_err(|e| {
+        if e.is_redirect() {
+            RustyError::Unexpected(
+                format!("{short_url} redirected more than {max_redirects} times")
+            )
+        } else {
+            RustyError::from(e)
+        }
+    })?;
+
+    let final_url = resp.url();
+    if final_url.host_str() != Some("open.spotify.com") {
+        return Err(
+            RustyError::Unexpected(
+                format!("{short_url} resolved to {final_url}, which is not an open.spotify.com URL")
+            )
+        );
+    }
+    Ok(final_url.to_string())
 }
 
-/// Extracts the Spotify ID and type (playlist or track) from a Spotify URL.
+/// Extracts the Spotify ID and type from a Spotify URL or URI.
 ///
 /// This function uses a regular expression to parse the URL and extract the resource type and ID.
+/// Tracks, albums, artists, playlists, shows, episodes and users are recognized, both in URLs
+/// such as `https://open.spotify.com/album/ID?si=abc` and in URIs such as `spotify:album:ID`.
 ///
 /// # Arguments
-/// * `url` - The Spotify URL to parse.
+/// * `url` - The Spotify URL or URI to parse.
 ///
 /// # Returns
 /// An `Option` containing a tuple with the resource type (`String`) and the ID (`String`),
@@ -72,16 +148,190 @@ pub async fn get_final_spotify_url(short_url: &str) -> Result<String, reqwest::E
 ///
 /// # Examples
 /// ```
+/// # use rustyspoty::extract_spotify_id_from_url;
 /// let url = "https://open.spotify.com/track/12345";
 /// let (kind, id) = extract_spotify_id_from_url(url).unwrap();
 /// assert_eq!(kind, "track");
 /// assert_eq!(id, "12345");
 /// ```
 pub fn extract_spotify_id_from_url(url: &str) -> Option<(String, String)> {
-    let re = Regex::new(r"spotify\.com/(playlist|track)/([a-zA-Z0-9]+)").unwrap();
+    let re = Regex::new(
+        r"(?:spotify\.com/(?:intl-[a-zA-Z-]+/)?|spotify:)(track|album|artist|playlist|show|episode|user)[/:]([a-zA-Z0-9]+)"
+    ).unwrap();
     re.captures(url).and_then(|caps| {
         let kind = caps.get(1)?.as_str().to_string();
         let id = caps.get(2)?.as_str().to_string();
         Some((kind, id))
     })
 }
+
+/// Parses a Spotify URI or `open.spotify.com` URL into its resource type and ID.
+///
+/// Accepts URIs such as `spotify:track:4iV5W9uYEdYUVa79Axb7Rh` and URLs such as
+/// `https://open.spotify.com/intl-de/track/4iV5W9uYEdYUVa79Axb7Rh?si=abc`, with or without the
+/// scheme and the localized path prefix.
+///
+/// # Arguments
+/// * `input` - The URI or URL to parse.
+///
+/// # Returns
+/// An `Option` containing a tuple with the resource type (e.g. `"track"`) and the ID, or `None`
+/// if `input` is neither a Spotify URI nor a Spotify URL, e.g. a bare ID.
+///
+/// # Examples
+/// ```
+/// # use rustyspoty::parse_spotify_uri;
+/// let (kind, id) = parse_spotify_uri("spotify:artist:4tZwfgrHOc3mvqYlEYSvVi").unwrap();
+/// assert_eq!(kind, "artist");
+/// assert_eq!(id, "4tZwfgrHOc3mvqYlEYSvVi");
+/// assert!(parse_spotify_uri("4tZwfgrHOc3mvqYlEYSvVi").is_none());
+/// ```
+pub fn parse_spotify_uri(input: &str) -> Option<(String, String)> {
+    let re = Regex::new(
+        r"^(?:spotify:|(?:https?://)?open\.spotify\.com/(?:intl-[a-zA-Z-]+/)?)(album|artist|track|playlist|show|episode|user)[:/]([a-zA-Z0-9]+)(?:[?#].*)?$"
+    ).unwrap();
+    re.captures(input.trim()).and_then(|caps| {
+        let kind = caps.get(1)?.as_str().to_string();
+        let id = caps.get(2)?.as_str().to_string();
+        Some((kind, id))
+    })
+}
+
+/// Reduces a bare Spotify ID, a Spotify URI or an `open.spotify.com` URL to the bare ID of an
+/// `expected_type` resource.
+///
+/// # Arguments
+/// * `input` - The ID, URI or URL, e.g. `spotify:track:4iV5W9uYEdYUVa79Axb7Rh`.
+/// * `expected_type` - The resource type the ID must be of, e.g. `"track"`.
+///
+/// # Errors
+/// Returns `RustyError::Io` with `InvalidInput` if `input` is a URI or URL of another resource
+/// type, or is neither a bare ID nor a Spotify URI or URL.
+///
+/// # Examples
+/// ```
+/// # use rustyspoty::normalize_id;
+/// let id = normalize_id("https://open.spotify.com/track/4iV5W9uYEdYUVa79Axb7Rh?si=abc", "track").unwrap();
+/// assert_eq!(id, "4iV5W9uYEdYUVa79Axb7Rh");
+/// assert!(normalize_id("spotify:album:1DFixLWuPkv3KT3TnV35m3", "track").is_err());
+/// ```
+pub fn normalize_id(input: &str, expected_type: &str) -> RustyResult<String> {
+    match parse_spotify_uri(input) {
+        Some((kind, id)) if kind == expected_type => Ok(id),
+        Some((kind, _)) => {
+            Err(RustyError::invalid_input(&format!("expected a {expected_type}, got the {kind} {input}")))
+        }
+        None if !input.is_empty() && input.chars().all(|c| c.is_ascii_alphanumeric()) => {
+            Ok(input.to_string())
+        }
+        None => {
+            Err(
+                RustyError::invalid_input(
+                    &format!("expected a {expected_type} ID, URI or URL, got {input}")
+                )
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::{ matchers::path, Mock, MockServer, ResponseTemplate };
+
+    #[tokio::test]
+    async fn test_redirect_loop_is_rejected() {
+        let server = MockServer::start().await;
+        Mock::given(path("/loop"))
+            .respond_with(ResponseTemplate::new(302).insert_header("Location", "/loop"))
+            .mount(&server).await;
+
+        let result = get_final_spotify_url_with_max_redirects(&format!("{}/loop", server.uri()), 3).await;
+        assert!(matches!(result, Err(RustyError::Unexpected(msg)) if msg.contains("more than 3 times")));
+    }
+
+    #[tokio::test]
+    async fn test_off_domain_final_url_is_rejected() {
+        let server = MockServer::start().await;
+        Mock::given(path("/short"))
+            .respond_with(ResponseTemplate::new(302).insert_header("Location", "/elsewhere"))
+            .mount(&server).await;
+        Mock::given(path("/elsewhere"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server).await;
+
+        let result = get_final_spotify_url(&format!("{}/short", server.uri())).await;
+        assert!(matches!(result, Err(RustyError::Unexpected(msg)) if msg.contains("not an open.spotify.com URL")));
+    }
+
+    #[test]
+    fn test_parse_spotify_uri() {
+        let expected = Some(("track".to_string(), "4iV5W9uYEdYUVa79Axb7Rh".to_string()));
+        assert_eq!(parse_spotify_uri("spotify:track:4iV5W9uYEdYUVa79Axb7Rh"), expected);
+        assert_eq!(parse_spotify_uri("https://open.spotify.com/track/4iV5W9uYEdYUVa79Axb7Rh?si=abc"), expected);
+        assert_eq!(parse_spotify_uri("open.spotify.com/intl-pt-br/track/4iV5W9uYEdYUVa79Axb7Rh"), expected);
+        assert_eq!(parse_spotify_uri("4iV5W9uYEdYUVa79Axb7Rh"), None);
+        assert_eq!(parse_spotify_uri("https://example.com/track/4iV5W9uYEdYUVa79Axb7Rh"), None);
+    }
+
+    #[test]
+    fn test_extract_spotify_id_from_url_recognizes_every_type() {
+        for kind in ["track", "album", "artist", "playlist", "show", "episode", "user"] {
+            let expected = Some((kind.to_string(), "4iV5W9uYEdYUVa79Axb7Rh".to_string()));
+            let url = format!("https://open.spotify.com/{kind}/4iV5W9uYEdYUVa79Axb7Rh");
+            assert_eq!(extract_spotify_id_from_url(&url), expected);
+            assert_eq!(extract_spotify_id_from_url(&format!("{url}?si=0123abcd")), expected);
+            let uri = format!("spotify:{kind}:4iV5W9uYEdYUVa79Axb7Rh");
+            assert_eq!(extract_spotify_id_from_url(&uri), expected);
+        }
+        assert_eq!(extract_spotify_id_from_url("https://open.spotify.com/genre/pop"), None);
+        assert_eq!(extract_spotify_id_from_url("4iV5W9uYEdYUVa79Axb7Rh"), None);
+    }
+
+    #[test]
+    fn test_normalize_id() {
+        let id = "4iV5W9uYEdYUVa79Axb7Rh";
+        assert_eq!(normalize_id(id, "track").unwrap(), id);
+        assert_eq!(normalize_id("spotify:track:4iV5W9uYEdYUVa79Axb7Rh", "track").unwrap(), id);
+        assert_eq!(
+            normalize_id("https://open.spotify.com/intl-de/track/4iV5W9uYEdYUVa79Axb7Rh?si=abc", "track").unwrap(),
+            id
+        );
+        assert!(
+            matches!(normalize_id("spotify:album:4iV5W9uYEdYUVa79Axb7Rh", "track"), Err(RustyError::Io(e)) if e.to_string().contains("got the album"))
+        );
+        assert!(normalize_id("not an id", "track").is_err());
+        assert!(normalize_id("", "track").is_err());
+    }
+
+    async fn respond_with(template: ResponseTemplate) -> reqwest::Response {
+        let server = MockServer::start().await;
+        Mock::given(path("/v1/tracks/track1")).respond_with(template).mount(&server).await;
+        reqwest::get(format!("{}/v1/tracks/track1", server.uri())).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_error_statuses_are_not_parsed_as_data() {
+        let response = respond_with(
+            ResponseTemplate::new(429).insert_header("Retry-After", "3")
+        ).await;
+        let result = parse_spotify_response::<serde_json::Value>(response).await;
+        assert!(matches!(result, Err(RustyError::SpotifyRateLimited(3))));
+
+        let response = respond_with(
+            ResponseTemplate::new(404).set_body_json(
+                serde_json::json!({ "error": { "status": 404, "message": "Non existing id" } })
+            )
+        ).await;
+        let result = parse_spotify_response::<serde_json::Value>(response).await;
+        assert!(
+            matches!(result, Err(RustyError::NotFound(message)) if message == "Non existing id")
+        );
+
+        let response = respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({ "id": "track1" }))
+        ).await;
+        let result = parse_spotify_response::<serde_json::Value>(response).await.unwrap();
+        assert_eq!(result["id"], "track1");
+    }
+}
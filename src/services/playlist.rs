@@ -1,10 +1,10 @@
 // src/queries/playlist.rs
 
-use crate::models::playlist::Playlist;
+use crate::{ models::playlist::Playlist, RustyResult };
 
 use super::get_spotify_data;
 
-pub async fn get_playlist_data(playlist_id: &str) -> Result<Playlist, reqwest::Error> {
+pub async fn get_playlist_data(playlist_id: &str) -> RustyResult<Playlist> {
     let url = format!("https://api.spotify.com/v1/playlists/{playlist_id}"); // Replace with the actual API endpoint
     get_spotify_data(&url).await
 }
@@ -1,19 +1,79 @@
-use crate::{ models::auth::{ ClientCredsAuthRequest, ClientCredsAuthResponse }, RustyError };
+use crate::{
+    auth_code_pkce::AccessTokenResponse,
+    models::auth::{ ClientCredsAuthRequest, ClientCredsAuthResponse },
+    RustyError,
+    SpotifyOAuth,
+};
 use std::time::{ SystemTime, UNIX_EPOCH };
+use tokio::sync::Mutex as AsyncMutex;
 
-/// Manages authentication tokens for Spotify API.
-///
-/// This struct is responsible for obtaining and refreshing Spotify access tokens
-/// as needed, using the client credentials grant flow.
-pub struct SpotifyTokenManager {
+/// The Spotify Accounts service endpoint issuing access tokens.
+const SPOTIFY_TOKEN_URL: &str = "https://accounts.spotify.com/api/token";
+
+/// How a [`SpotifyTokenManager`] obtains new access tokens.
+enum Grant {
+    /// The client credentials flow, authorizing the application itself.
+    ClientCredentials {
+        /// The Spotify API client secret.
+        client_secret: String,
+    },
+    /// A user's authorization, refreshed through the OAuth client that obtained it.
+    User {
+        /// The OAuth client the user authorized.
+        oauth: SpotifyOAuth,
+    },
+}
+
+/// The current token of a [`SpotifyTokenManager`].
+#[derive(Default)]
+struct TokenState {
     /// The current access token for API requests, if available.
     access_token: Option<String>,
     /// The UNIX timestamp at which the current access token expires.
     expires_at: Option<u64>,
+    /// The refresh token issued with the current user access token, if any.
+    refresh_token: Option<String>,
+}
+
+impl TokenState {
+    /// Checks if the stored access token is still valid.
+    ///
+    /// Compares the current time with the token's expiration time to determine validity.
+    fn is_token_valid(&self) -> bool {
+        self.expires_at
+            .map(|expiry| SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() < expiry)
+            .unwrap_or(false)
+    }
+
+    /// Stores a user access token, keeping the current refresh token if `token` has none.
+    fn set_user_token(&mut self, token: AccessTokenResponse) {
+        if token.refresh_token.is_some() {
+            self.refresh_token = token.refresh_token;
+        }
+        self.access_token = Some(token.access_token);
+        self.expires_at = Some(expires_at(token.expires_in as u64));
+    }
+}
+
+/// Manages authentication tokens for Spotify API.
+///
+/// This struct is responsible for obtaining and refreshing Spotify access tokens
+/// as needed, using the client credentials grant flow, or the refresh token of a user who
+/// authorized the application through [`SpotifyOAuth`].
+///
+/// The token is kept behind a lock, so a manager shared by concurrent requests refreshes an
+/// expired token only once, while the other requests wait for the new token.
+pub struct SpotifyTokenManager {
+    /// The current token, locked while it is refreshed.
+    state: AsyncMutex<TokenState>,
     /// The Spotify API client ID.
     client_id: String,
-    /// The Spotify API client secret.
-    client_secret: String,
+    /// How new access tokens are obtained.
+    grant: Grant,
+    /// The HTTP client used to reach the Spotify Accounts service.
+    http_client: reqwest::Client,
+    /// The token endpoint, overridden in tests to target a mock server.
+    token_url: String,
 }
 
 impl SpotifyTokenManager {
@@ -24,35 +84,126 @@ impl SpotifyTokenManager {
     /// * `client_id` - Your application's Spotify client ID.
     /// * `client_secret` - Your application's Spotify client secret.
     pub fn new(client_id: String, client_secret: String) -> Self {
+        Self::with_http_client(client_id, client_secret, reqwest::Client::new())
+    }
+
+    /// Creates a new `SpotifyTokenManager` that sends its token requests through `http_client`.
+    ///
+    /// # Arguments
+    ///
+    /// * `client_id` - Your application's Spotify client ID.
+    /// * `client_secret` - Your application's Spotify client secret.
+    /// * `http_client` - The `reqwest::Client` to use, e.g. one configured with a proxy.
+    pub fn with_http_client(
+        client_id: String,
+        client_secret: String,
+        http_client: reqwest::Client
+    ) -> Self {
         SpotifyTokenManager {
-            access_token: None,
-            expires_at: None,
+            state: AsyncMutex::new(TokenState::default()),
             client_id,
-            client_secret,
+            grant: Grant::ClientCredentials { client_secret },
+            http_client,
+            token_url: SPOTIFY_TOKEN_URL.to_string(),
         }
     }
 
-    /// Checks if the stored access token is still valid.
+    /// Creates a token manager for a user who authorized the application through `oauth`.
     ///
-    /// Compares the current time with the token's expiration time to determine validity.
-    fn is_token_valid(&self) -> bool {
-        self.expires_at
-            .map(|expiry| SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() < expiry)
-            .unwrap_or(false)
+    /// The manager starts with the access token of `token`, and refreshes it through `oauth`
+    /// with the refresh token once it expires.
+    ///
+    /// # Arguments
+    ///
+    /// * `oauth` - The OAuth client the user authorized, used for future refreshes.
+    /// * `token` - The token obtained from `oauth`.
+    pub(crate) fn for_user(oauth: SpotifyOAuth, token: AccessTokenResponse) -> Self {
+        let mut state = TokenState::default();
+        state.set_user_token(token);
+        Self::for_user_grant(oauth, state)
+    }
+
+    /// Creates a token manager for a user from a previously stored access token.
+    ///
+    /// # Arguments
+    ///
+    /// * `oauth` - The OAuth client used for future refreshes.
+    /// * `access_token` - The stored access token.
+    /// * `refresh_token` - The stored refresh token, if any.
+    /// * `expires_at` - The UNIX timestamp, in seconds, at which `access_token` expires.
+    pub(crate) fn for_stored_user_token(
+        oauth: SpotifyOAuth,
+        access_token: String,
+        refresh_token: Option<String>,
+        expires_at: u64
+    ) -> Self {
+        let state = TokenState {
+            access_token: Some(access_token),
+            // Replaced 60 seconds early, like the tokens obtained by the manager itself
+            expires_at: Some(expires_at.saturating_sub(60)),
+            refresh_token,
+        };
+        Self::for_user_grant(oauth, state)
+    }
+
+    /// Creates a token manager starting from `state`, refreshing through `oauth`.
+    fn for_user_grant(oauth: SpotifyOAuth, state: TokenState) -> Self {
+        SpotifyTokenManager {
+            state: AsyncMutex::new(state),
+            client_id: oauth.client_id().to_string(),
+            http_client: oauth.http_client().clone(),
+            token_url: SPOTIFY_TOKEN_URL.to_string(),
+            grant: Grant::User { oauth },
+        }
+    }
+
+    /// Whether the access tokens are issued on behalf of a user, rather than the application.
+    pub(crate) fn is_user(&self) -> bool {
+        matches!(self.grant, Grant::User { .. })
+    }
+
+    /// The HTTP client used to reach the Spotify Accounts service.
+    pub(crate) fn http_client(&self) -> &reqwest::Client {
+        &self.http_client
+    }
+
+    /// Points the token manager at another token endpoint, such as a mock server.
+    #[cfg(test)]
+    pub(crate) fn set_token_url(&mut self, token_url: String) {
+        if let Grant::User { oauth } = &mut self.grant {
+            oauth.set_token_url(token_url.clone());
+        }
+        self.token_url = token_url;
     }
 
     /// Requests a new access token from the Spotify Accounts service.
     ///
-    /// Uses the client credentials grant to obtain a new token and updates `access_token` and `expires_at`.
-    async fn request_new_token(&mut self) -> Result<(), RustyError> {
-        let client = reqwest::Client::new();
-        let response = client
-            .post("https://accounts.spotify.com/api/token")
+    /// Uses the client credentials grant, or the user's refresh token, to obtain a new token and
+    /// updates `access_token` and `expires_at` in `state`.
+    async fn request_new_token(&self, state: &mut TokenState) -> Result<(), RustyError> {
+        let client_secret = match &self.grant {
+            Grant::ClientCredentials { client_secret } => client_secret.clone(),
+            Grant::User { oauth } => {
+                let refresh_token = state.refresh_token
+                    .as_deref()
+                    .ok_or_else(|| {
+                        RustyError::TokenAuthentication(
+                            "the user access token expired and no refresh token is available".to_string()
+                        )
+                    })?;
+                let token = oauth.refresh_access_token(refresh_token).await?;
+                state.set_user_token(token);
+                return Ok(());
+            }
+        };
+
+        let response = self.http_client
+            .post(&self.token_url)
             .form(
                 &(ClientCredsAuthRequest {
                     grant_type: "client_credentials".to_owned(),
                     client_id: self.client_id.clone(),
-                    client_secret: self.client_secret.clone(),
+                    client_secret,
                 })
             )
             .send().await?;
@@ -68,11 +219,9 @@ impl SpotifyTokenManager {
 
         let res = response.json::<ClientCredsAuthResponse>().await?;
 
-        // Update the token and expiration time, subtracting 60 seconds to account for potential timing issues
-        self.access_token = Some(res.access_token);
-        self.expires_at = Some(
-            SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() + res.expires_in - 60
-        );
+        // Update the token and expiration time
+        state.access_token = Some(res.access_token);
+        state.expires_at = Some(expires_at(res.expires_in));
 
         Ok(())
     }
@@ -81,10 +230,62 @@ impl SpotifyTokenManager {
     ///
     /// Checks the validity of the current token and requests a new one if necessary.
     /// Returns the current token if it's valid, or a new one if it was refreshed.
-    pub async fn get_valid_token(&mut self) -> Result<String, RustyError> {
-        if !self.is_token_valid() {
-            self.request_new_token().await?;
+    ///
+    /// Concurrent callers finding the token expired wait for the first one's refresh and share
+    /// its new token, instead of each requesting one.
+    pub async fn get_valid_token(&self) -> Result<String, RustyError> {
+        // Holding the lock during the refresh makes the other callers wait for its result
+        let mut state = self.state.lock().await;
+        if !state.is_token_valid() {
+            self.request_new_token(&mut state).await?;
+        }
+        Ok(state.access_token.clone().unwrap()) // Safe unwrap because request_new_token() ensures access_token is Some
+    }
+}
+
+/// Computes the UNIX timestamp at which a token valid for `expires_in` seconds should be replaced,
+/// subtracting 60 seconds to account for potential timing issues.
+fn expires_at(expires_in: u64) -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() + expires_in.saturating_sub(60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use wiremock::{ matchers::{ method, path }, Mock, MockServer, ResponseTemplate };
+
+    #[tokio::test]
+    async fn test_concurrent_callers_share_a_single_token_request() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/token"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(
+                        serde_json::json!({
+                            "access_token": "test_token",
+                            "token_type": "Bearer",
+                            "expires_in": 3600
+                        })
+                    )
+                    .set_delay(std::time::Duration::from_millis(50))
+            )
+            .expect(1)
+            .mount(&server).await;
+        let mut token_manager = SpotifyTokenManager::new(
+            "client_id".to_string(),
+            "client_secret".to_string()
+        );
+        token_manager.set_token_url(format!("{}/api/token", server.uri()));
+        let token_manager = Arc::new(token_manager);
+
+        let requests = (0..4).map(|_| {
+            let token_manager = Arc::clone(&token_manager);
+            tokio::spawn(async move { token_manager.get_valid_token().await })
+        });
+        for request in futures::future::join_all(requests).await {
+            assert_eq!(request.unwrap().unwrap(), "test_token");
         }
-        Ok(self.access_token.clone().unwrap()) // Safe unwrap because request_new_token() ensures access_token is Some
     }
 }
@@ -0,0 +1,1346 @@
+use std::ops::{ Deref, DerefMut };
+
+use reqwest::Method;
+use serde::de::DeserializeOwned;
+
+use crate::{
+    auth_code_pkce::AccessTokenResponse,
+    models::{
+        artist::{ Artist, FollowedArtists },
+        market::Market,
+        page::{ CursorBasedPage, Page },
+        player::{ CurrentlyPlaying, PlayHistory },
+        playlist::{ Playlist, SnapshotResponse },
+        track::{ SavedTrack, Track },
+        user::{ FollowType, PrivateUser, TimeRange },
+    },
+    RustyError,
+    RustyResult,
+    SpotifyClientCredentials,
+    SpotifyOAuth,
+    SpotifyTokenManager,
+};
+
+/// The most track IDs the library endpoints accept per request.
+const MAX_LIBRARY_IDS: usize = 50;
+
+/// The most artist or user IDs the follow endpoints accept per request.
+const MAX_FOLLOW_IDS: usize = 50;
+
+/// The most item URIs the playlist items endpoints accept per request.
+const MAX_PLAYLIST_URIS: usize = 100;
+
+/// A client authorized on behalf of a Spotify user through the Authorization Code with PKCE Flow.
+///
+/// Usually obtained from [`SpotifyOAuth::exchange_code_into_client`], or restored from a stored
+/// token with [`from_user_token`](Self::from_user_token). The client refreshes its access token
+/// with the user's refresh token once it expires.
+///
+/// Every method of [`SpotifyClientCredentials`] is available through `Deref`, sharing its cache.
+/// Unlike a client authorized with client credentials, a user client accepts the `from_token`
+/// market, which stands for the market of the user's account.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use rustyspoty::{ models::market::Market, RustyResult, SpotifyOAuth };
+/// # async fn run(oauth: SpotifyOAuth, code: &str) -> RustyResult<()> {
+/// let user_client = oauth.exchange_code_into_client(code).await?;
+/// let top_tracks = user_client.get_artist_top_tracks("0TnOYISbd1XYRBk9myaseg", Some(Market::FromToken)).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct SpotifyUserClient {
+    /// The underlying client, whose token manager refreshes the user's access token.
+    client: SpotifyClientCredentials,
+}
+
+impl SpotifyUserClient {
+    /// Creates a client authorized by a token the user granted through `oauth`.
+    ///
+    /// # Arguments
+    ///
+    /// * `oauth` - The OAuth client the user authorized, used to refresh the access token.
+    /// * `token` - The token obtained from `oauth`, e.g. with `request_access_token`.
+    pub fn new(oauth: SpotifyOAuth, token: AccessTokenResponse) -> Self {
+        Self::from_token_manager(SpotifyTokenManager::for_user(oauth, token))
+    }
+
+    /// Creates a client authorized by a user token stored earlier, e.g. to resume a session after
+    /// the application restarted.
+    ///
+    /// The client refreshes the access token once it expires, if a refresh token is given.
+    /// Otherwise, requests fail with `RustyError::TokenAuthentication` after it expired.
+    ///
+    /// # Arguments
+    ///
+    /// * `client_id` - The Spotify client ID the user authorized.
+    /// * `access_token` - The user's access token.
+    /// * `refresh_token` - The user's refresh token, if any.
+    /// * `expires_at` - The UNIX timestamp, in seconds, at which `access_token` expires, as
+    ///   returned by [`AccessTokenResponse::expires_at_unix`] when the token was obtained.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rustyspoty::{ RustyResult, SpotifyUserClient };
+    /// # async fn run(access_token: String, refresh_token: String, expires_at: u64) -> RustyResult<()> {
+    /// let user_client = SpotifyUserClient::from_user_token(
+    ///     "your_spotify_client_id".to_string(),
+    ///     access_token,
+    ///     Some(refresh_token),
+    ///     expires_at
+    /// );
+    /// let album = user_client.get_album("4aawyAB9vmqN3uQ7FjRGTy").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_user_token(
+        client_id: String,
+        access_token: String,
+        refresh_token: Option<String>,
+        expires_at: u64
+    ) -> Self {
+        // Refreshing a token needs neither a redirect URI nor scopes
+        let oauth = SpotifyOAuth::new(client_id, String::new(), String::new());
+        let token_manager = SpotifyTokenManager::for_stored_user_token(
+            oauth,
+            access_token,
+            refresh_token,
+            expires_at
+        );
+        Self::from_token_manager(token_manager)
+    }
+
+    /// Creates a client authorized by `token_manager`, sending its requests through the token
+    /// manager's HTTP client.
+    fn from_token_manager(token_manager: SpotifyTokenManager) -> Self {
+        let http_client = token_manager.http_client().clone();
+        SpotifyUserClient {
+            client: SpotifyClientCredentials::from_token_manager(token_manager, http_client),
+        }
+    }
+
+    /// Fetches the profile of the user who authorized the client, including its private fields.
+    ///
+    /// The email, country and product are only returned with the `user-read-email` and
+    /// `user-read-private` scopes.
+    ///
+    /// # Caching
+    ///
+    /// The profile is never cached: it holds the user's email address, which should not outlive
+    /// the request in a cache shared with other data, or in a shared cache backend.
+    pub async fn get_current_user(&self) -> RustyResult<PrivateUser> {
+        self.client.send_spotify_request::<_, ()>(Method::GET, "/me", None).await
+    }
+
+    /// Fetches a page of the tracks saved in the user's library, most recently saved first.
+    ///
+    /// Requires the `user-library-read` scope. The library is never cached, so tracks saved or
+    /// removed in the meantime show up right away.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - The maximum number of tracks to return, clamped to 1-50. Defaults to 20.
+    /// * `offset` - The index of the first track to return. Defaults to 0.
+    /// * `market` - An optional market to relink the tracks to, e.g. `Market::FromToken`. Falls
+    ///   back to the client's default market when `None`.
+    pub async fn get_saved_tracks(
+        &self,
+        limit: Option<u32>,
+        offset: Option<u32>,
+        market: Option<Market>
+    ) -> RustyResult<Page<SavedTrack>> {
+        let limit = limit.unwrap_or(20).clamp(1, 50);
+        let offset = offset.unwrap_or(0);
+        let mut path = format!("/me/tracks?limit={limit}&offset={offset}");
+        if let Some(market) = self.client.resolve_market(market)? {
+            path.push_str(&format!("&market={market}"));
+        }
+        self.client.send_spotify_request::<_, ()>(Method::GET, &path, None).await
+    }
+
+    /// Fetches a page of the user's most played tracks. Requires the `user-top-read` scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `time_range` - The period to compute the top tracks over. Defaults to
+    ///   `TimeRange::MediumTerm`.
+    /// * `limit` - The maximum number of tracks to return, clamped to 1-50. Defaults to 20.
+    /// * `offset` - The index of the first track to return. Defaults to 0.
+    pub async fn get_top_tracks(
+        &self,
+        time_range: Option<TimeRange>,
+        limit: Option<u32>,
+        offset: Option<u32>
+    ) -> RustyResult<Page<Track>> {
+        self.get_top_items("tracks", time_range, limit, offset).await
+    }
+
+    /// Fetches a page of the user's most played artists. Requires the `user-top-read` scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `time_range` - The period to compute the top artists over. Defaults to
+    ///   `TimeRange::MediumTerm`.
+    /// * `limit` - The maximum number of artists to return, clamped to 1-50. Defaults to 20.
+    /// * `offset` - The index of the first artist to return. Defaults to 0.
+    pub async fn get_top_artists(
+        &self,
+        time_range: Option<TimeRange>,
+        limit: Option<u32>,
+        offset: Option<u32>
+    ) -> RustyResult<Page<Artist>> {
+        self.get_top_items("artists", time_range, limit, offset).await
+    }
+
+    /// Fetches a page of the user's top items of `item_type`, `"tracks"` or `"artists"`.
+    ///
+    /// The top items are never cached, as they reflect the user's listening up to now.
+    async fn get_top_items<T: DeserializeOwned>(
+        &self,
+        item_type: &str,
+        time_range: Option<TimeRange>,
+        limit: Option<u32>,
+        offset: Option<u32>
+    ) -> RustyResult<Page<T>> {
+        let time_range = time_range.unwrap_or_default().as_str();
+        let limit = limit.unwrap_or(20).clamp(1, 50);
+        let offset = offset.unwrap_or(0);
+        let path = format!(
+            "/me/top/{item_type}?time_range={time_range}&limit={limit}&offset={offset}"
+        );
+        self.client.send_spotify_request::<_, ()>(Method::GET, &path, None).await
+    }
+
+    /// Saves tracks to the user's library. Requires the `user-library-modify` scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `track_ids` - The Spotify IDs of the tracks, sent in requests of up to 50 IDs.
+    pub async fn save_tracks(&self, track_ids: &[String]) -> RustyResult<()> {
+        for chunk in track_ids.chunks(MAX_LIBRARY_IDS) {
+            let body = serde_json::json!({ "ids": chunk });
+            self.client.put_spotify_data::<(), _>("/me/tracks", &body).await?;
+        }
+        Ok(())
+    }
+
+    /// Removes tracks from the user's library. Requires the `user-library-modify` scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `track_ids` - The Spotify IDs of the tracks, sent in requests of up to 50 IDs.
+    pub async fn remove_saved_tracks(&self, track_ids: &[String]) -> RustyResult<()> {
+        for chunk in track_ids.chunks(MAX_LIBRARY_IDS) {
+            let body = serde_json::json!({ "ids": chunk });
+            self.client.delete_spotify_data::<(), _>("/me/tracks", &body).await?;
+        }
+        Ok(())
+    }
+
+    /// Checks which tracks are saved in the user's library. Requires the `user-library-read` scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `track_ids` - The Spotify IDs of the tracks, sent in requests of up to 50 IDs.
+    ///
+    /// # Returns
+    ///
+    /// Whether each track is saved, in the order of `track_ids`.
+    pub async fn check_saved_tracks(&self, track_ids: &[String]) -> RustyResult<Vec<bool>> {
+        let mut saved = Vec::with_capacity(track_ids.len());
+        for chunk in track_ids.chunks(MAX_LIBRARY_IDS) {
+            let path = format!("/me/tracks/contains?ids={}", chunk.join(","));
+            let contains: Vec<bool> = self.client.send_spotify_request::<_, ()>(
+                Method::GET,
+                &path,
+                None
+            ).await?;
+            saved.extend(contains);
+        }
+        Ok(saved)
+    }
+
+    /// Fetches a page of the artists the user follows. Requires the `user-follow-read` scope.
+    ///
+    /// Pass the `after` cursor of the returned page to fetch the next one. The followed artists
+    /// are never cached, so artists followed or unfollowed in the meantime show up right away.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - The maximum number of artists to return, clamped to 1-50. Defaults to 20.
+    /// * `after` - The ID of the last artist of the previous page.
+    pub async fn get_followed_artists(
+        &self,
+        limit: Option<u32>,
+        after: Option<&str>
+    ) -> RustyResult<CursorBasedPage<Artist>> {
+        let limit = limit.unwrap_or(20).clamp(1, 50);
+        let mut path = format!("/me/following?type=artist&limit={limit}");
+        if let Some(after) = after {
+            path.push_str(&format!("&after={after}"));
+        }
+        let followed: FollowedArtists = self.client.send_spotify_request::<_, ()>(
+            Method::GET,
+            &path,
+            None
+        ).await?;
+        Ok(followed.artists)
+    }
+
+    /// Follows artists. Requires the `user-follow-modify` scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - The Spotify IDs of the artists, at most 50.
+    pub async fn follow_artists(&self, ids: &[String]) -> RustyResult<()> {
+        self.set_following(Method::PUT, FollowType::Artist, ids).await
+    }
+
+    /// Unfollows artists. Requires the `user-follow-modify` scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - The Spotify IDs of the artists, at most 50.
+    pub async fn unfollow_artists(&self, ids: &[String]) -> RustyResult<()> {
+        self.set_following(Method::DELETE, FollowType::Artist, ids).await
+    }
+
+    /// Follows users. Requires the `user-follow-modify` scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - The Spotify IDs of the users, at most 50.
+    pub async fn follow_users(&self, ids: &[String]) -> RustyResult<()> {
+        self.set_following(Method::PUT, FollowType::User, ids).await
+    }
+
+    /// Unfollows users. Requires the `user-follow-modify` scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - The Spotify IDs of the users, at most 50.
+    pub async fn unfollow_users(&self, ids: &[String]) -> RustyResult<()> {
+        self.set_following(Method::DELETE, FollowType::User, ids).await
+    }
+
+    /// Checks which artists or users the user follows. Requires the `user-follow-read` scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `follow_type` - Whether `ids` are artist or user IDs.
+    /// * `ids` - The Spotify IDs of the artists or users, at most 50.
+    ///
+    /// # Returns
+    ///
+    /// Whether each artist or user is followed, in the order of `ids`.
+    pub async fn check_following(
+        &self,
+        follow_type: FollowType,
+        ids: &[String]
+    ) -> RustyResult<Vec<bool>> {
+        check_follow_ids(ids)?;
+        let path = format!(
+            "/me/following/contains?type={}&ids={}",
+            follow_type.as_str(),
+            ids.join(",")
+        );
+        self.client.send_spotify_request::<_, ()>(Method::GET, &path, None).await
+    }
+
+    /// Follows (`PUT`) or unfollows (`DELETE`) the artists or users `ids`.
+    async fn set_following(
+        &self,
+        method: Method,
+        follow_type: FollowType,
+        ids: &[String]
+    ) -> RustyResult<()> {
+        check_follow_ids(ids)?;
+        let path = format!("/me/following?type={}", follow_type.as_str());
+        let body = serde_json::json!({ "ids": ids });
+        self.client.send_spotify_request(method, &path, Some(&body)).await
+    }
+
+    /// Creates a playlist for a user. Requires the `playlist-modify-public` scope, or
+    /// `playlist-modify-private` for a private playlist.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The Spotify ID of the user, who must be the user who authorized the client.
+    /// * `name` - The name of the playlist.
+    /// * `public` - Whether the playlist shows up on the user's profile.
+    /// * `collaborative` - Whether other users can edit the playlist. A collaborative playlist
+    ///   must not be public.
+    /// * `description` - An optional description of the playlist.
+    pub async fn create_playlist(
+        &self,
+        user_id: &str,
+        name: &str,
+        public: bool,
+        collaborative: bool,
+        description: Option<&str>
+    ) -> RustyResult<Playlist> {
+        let mut body = serde_json::json!({
+            "name": name,
+            "public": public,
+            "collaborative": collaborative,
+        });
+        if let Some(description) = description {
+            body["description"] = description.into();
+        }
+        self.client.post_spotify_data(&format!("/users/{user_id}/playlists"), &body).await
+    }
+
+    /// Adds items to a playlist and invalidates its cached responses. Requires the
+    /// `playlist-modify-public` or `playlist-modify-private` scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `playlist_id` - The Spotify ID of the playlist.
+    /// * `uris` - The Spotify URIs of the tracks or episodes to add, at most 100.
+    /// * `position` - The zero-based index to insert the items at. Appends them when `None`.
+    /// * `snapshot_id` - The snapshot ID of the playlist version the edit is based on, e.g. from
+    ///   the last edit. Edits the latest version when `None`.
+    ///
+    /// # Returns
+    ///
+    /// The snapshot ID of the edited playlist, or `RustyError::Io` with `InvalidInput` when more
+    /// than 100 URIs are given. Returns `RustyError::SnapshotConflict` when Spotify rejects
+    /// `snapshot_id`, so the playlist can be refetched and the edit retried.
+    pub async fn add_items_to_playlist(
+        &self,
+        playlist_id: &str,
+        uris: &[String],
+        position: Option<u32>,
+        snapshot_id: Option<&str>
+    ) -> RustyResult<SnapshotResponse> {
+        check_playlist_uris(uris)?;
+        let mut body = serde_json::json!({ "uris": uris });
+        if let Some(position) = position {
+            body["position"] = position.into();
+        }
+        if let Some(snapshot_id) = snapshot_id {
+            body["snapshot_id"] = snapshot_id.into();
+        }
+        let path = format!("/playlists/{playlist_id}/tracks");
+        let snapshot = self.client.post_spotify_data(&path, &body).await;
+        self.client.invalidate_playlist(playlist_id).await;
+        snapshot
+    }
+
+    /// Removes every occurrence of items from a playlist and invalidates its cached responses.
+    /// Requires the `playlist-modify-public` or `playlist-modify-private` scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `playlist_id` - The Spotify ID of the playlist.
+    /// * `uris` - The Spotify URIs of the tracks or episodes to remove, at most 100.
+    /// * `snapshot_id` - The snapshot ID of the playlist version the edit is based on, e.g. from
+    ///   the last edit. Edits the latest version when `None`.
+    ///
+    /// # Returns
+    ///
+    /// The snapshot ID of the edited playlist, or `RustyError::Io` with `InvalidInput` when more
+    /// than 100 URIs are given. Returns `RustyError::SnapshotConflict` when Spotify rejects
+    /// `snapshot_id`, so the playlist can be refetched and the edit retried.
+    pub async fn remove_playlist_items(
+        &self,
+        playlist_id: &str,
+        uris: &[String],
+        snapshot_id: Option<&str>
+    ) -> RustyResult<SnapshotResponse> {
+        check_playlist_uris(uris)?;
+        let tracks: Vec<_> = uris
+            .iter()
+            .map(|uri| serde_json::json!({ "uri": uri }))
+            .collect();
+        let mut body = serde_json::json!({ "tracks": tracks });
+        if let Some(snapshot_id) = snapshot_id {
+            body["snapshot_id"] = snapshot_id.into();
+        }
+        let path = format!("/playlists/{playlist_id}/tracks");
+        let snapshot = self.client.delete_spotify_data(&path, &body).await;
+        self.client.invalidate_playlist(playlist_id).await;
+        snapshot
+    }
+
+    /// Fetches the state of the user's playback. Requires the `user-read-playback-state` scope.
+    ///
+    /// The state is never cached, as it changes from one moment to the next.
+    ///
+    /// # Arguments
+    ///
+    /// * `market` - An optional market to relink the playing track to, e.g. `Market::FromToken`.
+    ///   Falls back to the client's default market when `None`.
+    ///
+    /// # Returns
+    ///
+    /// The playback state, or `None` when nothing is playing on any of the user's devices.
+    pub async fn get_playback_state(
+        &self,
+        market: Option<Market>
+    ) -> RustyResult<Option<CurrentlyPlaying>> {
+        // Episodes are only returned when asked for
+        let mut path = "/me/player?additional_types=track,episode".to_string();
+        if let Some(market) = self.client.resolve_market(market)? {
+            path.push_str(&format!("&market={market}"));
+        }
+        self.client.send_spotify_request::<_, ()>(Method::GET, &path, None).await
+    }
+
+    /// Fetches the tracks the user played recently, most recent first. Requires the
+    /// `user-read-recently-played` scope.
+    ///
+    /// Follow the `before` cursor of the returned page to go further back in time. The history is
+    /// never cached.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - The maximum number of tracks to return, clamped to 1-50. Defaults to 20.
+    /// * `before` - Only return tracks played before this UNIX timestamp, in milliseconds.
+    /// * `after` - Only return tracks played after this UNIX timestamp, in milliseconds.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RustyError::Io` with `InvalidInput` if both `before` and `after` are given.
+    pub async fn get_recently_played(
+        &self,
+        limit: Option<u32>,
+        before: Option<u64>,
+        after: Option<u64>
+    ) -> RustyResult<CursorBasedPage<PlayHistory>> {
+        let limit = limit.unwrap_or(20).clamp(1, 50);
+        let mut path = format!("/me/player/recently-played?limit={limit}");
+        match (before, after) {
+            (Some(_), Some(_)) => {
+                return Err(RustyError::invalid_input("`before` and `after` cannot both be set"));
+            }
+            (Some(before), None) => path.push_str(&format!("&before={before}")),
+            (None, Some(after)) => path.push_str(&format!("&after={after}")),
+            (None, None) => {}
+        }
+        self.client.send_spotify_request::<_, ()>(Method::GET, &path, None).await
+    }
+
+    /// Starts a new playback, or resumes the paused one. Requires the `user-modify-playback-state`
+    /// scope and a Spotify Premium account, like every player command.
+    ///
+    /// # Arguments
+    ///
+    /// * `device_id` - The device to play on. Defaults to the active device.
+    /// * `context_uri` - The Spotify URI of an album, artist or playlist to play.
+    /// * `uris` - The Spotify URIs of the tracks to play, instead of a context.
+    ///
+    /// The paused playback is resumed when neither `context_uri` nor `uris` is given.
+    pub async fn start_resume_playback(
+        &self,
+        device_id: Option<&str>,
+        context_uri: Option<&str>,
+        uris: Option<&[String]>
+    ) -> RustyResult<()> {
+        let mut body = serde_json::json!({});
+        if let Some(context_uri) = context_uri {
+            body["context_uri"] = context_uri.into();
+        }
+        if let Some(uris) = uris {
+            body["uris"] = uris.into();
+        }
+        let path = player_path("/me/player/play", &[], device_id);
+        self.client.put_spotify_data(&path, &body).await
+    }
+
+    /// Pauses the playback.
+    ///
+    /// # Arguments
+    ///
+    /// * `device_id` - The device to pause. Defaults to the active device.
+    pub async fn pause_playback(&self, device_id: Option<&str>) -> RustyResult<()> {
+        let path = player_path("/me/player/pause", &[], device_id);
+        self.client.send_spotify_request::<_, ()>(Method::PUT, &path, None).await
+    }
+
+    /// Skips to the next item in the user's queue.
+    ///
+    /// # Arguments
+    ///
+    /// * `device_id` - The device to skip on. Defaults to the active device.
+    pub async fn skip_to_next(&self, device_id: Option<&str>) -> RustyResult<()> {
+        let path = player_path("/me/player/next", &[], device_id);
+        self.client.send_spotify_request::<_, ()>(Method::POST, &path, None).await
+    }
+
+    /// Skips to the previous item in the user's queue.
+    ///
+    /// # Arguments
+    ///
+    /// * `device_id` - The device to skip on. Defaults to the active device.
+    pub async fn skip_to_previous(&self, device_id: Option<&str>) -> RustyResult<()> {
+        let path = player_path("/me/player/previous", &[], device_id);
+        self.client.send_spotify_request::<_, ()>(Method::POST, &path, None).await
+    }
+
+    /// Seeks to a position in the playing item.
+    ///
+    /// # Arguments
+    ///
+    /// * `position_ms` - The position, in milliseconds. Seeking past the end of the item skips to
+    ///   the next one.
+    /// * `device_id` - The device to seek on. Defaults to the active device.
+    pub async fn seek(&self, position_ms: u64, device_id: Option<&str>) -> RustyResult<()> {
+        let position_ms = position_ms.to_string();
+        let path = player_path("/me/player/seek", &[("position_ms", &position_ms)], device_id);
+        self.client.send_spotify_request::<_, ()>(Method::PUT, &path, None).await
+    }
+
+    /// Sets the volume of the playback.
+    ///
+    /// # Arguments
+    ///
+    /// * `percent` - The volume, from 0 to 100.
+    /// * `device_id` - The device to set the volume of. Defaults to the active device.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RustyError::Io` with `InvalidInput` if `percent` is above 100.
+    pub async fn set_volume(&self, percent: u8, device_id: Option<&str>) -> RustyResult<()> {
+        if percent > 100 {
+            return Err(RustyError::invalid_input(&format!("volume must be 0-100, got {percent}")));
+        }
+        let percent = percent.to_string();
+        let path = player_path("/me/player/volume", &[("volume_percent", &percent)], device_id);
+        self.client.send_spotify_request::<_, ()>(Method::PUT, &path, None).await
+    }
+
+    /// Turns shuffle on or off.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - Whether to shuffle the user's queue.
+    /// * `device_id` - The device to shuffle on. Defaults to the active device.
+    pub async fn toggle_shuffle(&self, state: bool, device_id: Option<&str>) -> RustyResult<()> {
+        let state = state.to_string();
+        let path = player_path("/me/player/shuffle", &[("state", &state)], device_id);
+        self.client.send_spotify_request::<_, ()>(Method::PUT, &path, None).await
+    }
+
+    /// Shuts the client down, like [`SpotifyClientCredentials::shutdown`].
+    pub async fn shutdown(self) {
+        self.client.shutdown().await;
+    }
+}
+
+/// Builds the path of a player command from its query parameters and the optional target device.
+fn player_path(path: &str, params: &[(&str, &str)], device_id: Option<&str>) -> String {
+    let mut query: Vec<String> = params
+        .iter()
+        .map(|(name, value)| format!("{name}={value}"))
+        .collect();
+    if let Some(device_id) = device_id {
+        query.push(format!("device_id={device_id}"));
+    }
+    if query.is_empty() { path.to_string() } else { format!("{path}?{}", query.join("&")) }
+}
+
+/// Rejects more IDs than the follow endpoints accept in a request.
+fn check_follow_ids(ids: &[String]) -> RustyResult<()> {
+    if ids.len() > MAX_FOLLOW_IDS {
+        return Err(
+            RustyError::invalid_input(
+                &format!("at most {MAX_FOLLOW_IDS} IDs can be sent per request, got {}", ids.len())
+            )
+        );
+    }
+    Ok(())
+}
+
+/// Rejects more URIs than the playlist items endpoints accept in a request.
+fn check_playlist_uris(uris: &[String]) -> RustyResult<()> {
+    if uris.len() > MAX_PLAYLIST_URIS {
+        return Err(
+            RustyError::invalid_input(
+                &format!(
+                    "at most {MAX_PLAYLIST_URIS} URIs can be sent per request, got {}",
+                    uris.len()
+                )
+            )
+        );
+    }
+    Ok(())
+}
+
+impl Deref for SpotifyUserClient {
+    type Target = SpotifyClientCredentials;
+
+    fn deref(&self) -> &Self::Target {
+        &self.client
+    }
+}
+
+impl DerefMut for SpotifyUserClient {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.client
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::player::PlayingItem;
+    use wiremock::{
+        matchers::{ body_json, body_string_contains, header, method, path, query_param },
+        Mock,
+        MockServer,
+        ResponseTemplate,
+    };
+
+    /// Creates a user client holding `access_token`, valid for `expires_in` seconds.
+    fn user_client(server: &MockServer, access_token: &str, expires_in: usize) -> SpotifyUserClient {
+        let oauth = SpotifyOAuth::new(
+            "client_id".to_string(),
+            "http://localhost:8888/callback".to_string(),
+            "user-read-private".to_string()
+        );
+        let token = AccessTokenResponse {
+            access_token: access_token.to_string(),
+            token_type: "Bearer".to_string(),
+            scope: "user-read-private".to_string(),
+            expires_in,
+            refresh_token: Some("refresh_token".to_string()),
+        };
+        let mut client = SpotifyUserClient::new(oauth, token);
+        client.use_mock_server(&server.uri());
+        client
+    }
+
+    fn playlist_json(playlist_id: &str, total: u32) -> serde_json::Value {
+        serde_json::json!({
+            "collaborative": false,
+            "description": null,
+            "external_urls": { "spotify": format!("https://open.spotify.com/playlist/{playlist_id}") },
+            "followers": { "href": null, "total": 0 },
+            "href": format!("https://api.spotify.com/v1/playlists/{playlist_id}"),
+            "id": playlist_id,
+            "images": [],
+            "name": "Road Trip",
+            "owner": {
+                "external_urls": { "spotify": "https://open.spotify.com/user/smedjan" },
+                "href": "https://api.spotify.com/v1/users/smedjan",
+                "id": "smedjan",
+                "type": "user",
+                "uri": "spotify:user:smedjan"
+            },
+            "public": true,
+            "snapshot_id": "snapshot1",
+            "tracks": { "items": [], "total": total },
+            "type": "playlist",
+            "uri": format!("spotify:playlist:{playlist_id}")
+        })
+    }
+
+    #[tokio::test]
+    async fn test_create_playlist() {
+        let server = MockServer::start().await;
+        let client = user_client(&server, "user_token", 3600);
+        Mock::given(method("POST"))
+            .and(path("/v1/users/smedjan/playlists"))
+            .and(
+                body_json(
+                    serde_json::json!({
+                        "name": "Road Trip",
+                        "public": false,
+                        "collaborative": true,
+                        "description": "Songs for the drive"
+                    })
+                )
+            )
+            .respond_with(ResponseTemplate::new(201).set_body_json(playlist_json("playlist1", 0)))
+            .expect(1)
+            .mount(&server).await;
+
+        let playlist = client
+            .create_playlist("smedjan", "Road Trip", false, true, Some("Songs for the drive")).await
+            .unwrap();
+        assert_eq!(playlist.id, "playlist1");
+    }
+
+    #[tokio::test]
+    async fn test_add_items_to_playlist_refetches_playlist() {
+        let server = MockServer::start().await;
+        let client = user_client(&server, "user_token", 3600);
+        let uris = vec!["spotify:track:track1".to_string()];
+        Mock::given(method("GET"))
+            .and(path("/v1/playlists/playlist1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(playlist_json("playlist1", 0)))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/playlists/playlist1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(playlist_json("playlist1", 1)))
+            .expect(1)
+            .mount(&server).await;
+        Mock::given(method("POST"))
+            .and(path("/v1/playlists/playlist1/tracks"))
+            .and(body_json(serde_json::json!({ "uris": ["spotify:track:track1"], "position": 0 })))
+            .respond_with(
+                ResponseTemplate::new(201).set_body_json(
+                    serde_json::json!({ "snapshot_id": "snapshot2" })
+                )
+            )
+            .expect(1)
+            .mount(&server).await;
+
+        assert_eq!(client.get_playlist("playlist1").await.unwrap().tracks.total, 0);
+        let snapshot = client
+            .add_items_to_playlist("playlist1", &uris, Some(0), None).await
+            .unwrap();
+        assert_eq!(snapshot.snapshot_id, "snapshot2");
+        assert_eq!(client.get_playlist("playlist1").await.unwrap().tracks.total, 1);
+    }
+
+    #[tokio::test]
+    async fn test_remove_playlist_items() {
+        let server = MockServer::start().await;
+        let client = user_client(&server, "user_token", 3600);
+        let uris = vec!["spotify:track:track1".to_string(), "spotify:episode:episode1".to_string()];
+        Mock::given(method("DELETE"))
+            .and(path("/v1/playlists/playlist1/tracks"))
+            .and(
+                body_json(
+                    serde_json::json!({
+                        "tracks": [
+                            { "uri": "spotify:track:track1" },
+                            { "uri": "spotify:episode:episode1" }
+                        ]
+                    })
+                )
+            )
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({ "snapshot_id": "snapshot2" })
+                )
+            )
+            .expect(1)
+            .mount(&server).await;
+
+        let snapshot = client.remove_playlist_items("playlist1", &uris, None).await.unwrap();
+        assert_eq!(snapshot.snapshot_id, "snapshot2");
+    }
+
+    #[tokio::test]
+    async fn test_stale_snapshot_is_a_snapshot_conflict() {
+        let server = MockServer::start().await;
+        let client = user_client(&server, "user_token", 3600);
+        let uris = vec!["spotify:track:track1".to_string()];
+        Mock::given(method("DELETE"))
+            .and(path("/v1/playlists/playlist1/tracks"))
+            .and(
+                body_json(
+                    serde_json::json!({
+                        "tracks": [{ "uri": "spotify:track:track1" }],
+                        "snapshot_id": "snapshot1"
+                    })
+                )
+            )
+            .respond_with(
+                ResponseTemplate::new(400).set_body_json(
+                    serde_json::json!({
+                        "error": { "status": 400, "message": "Invalid snapshot id" }
+                    })
+                )
+            )
+            .expect(1)
+            .mount(&server).await;
+
+        let removed = client.remove_playlist_items("playlist1", &uris, Some("snapshot1")).await;
+        assert!(
+            matches!(removed, Err(RustyError::SnapshotConflict(body)) if body.contains("snapshot"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_playlist_items_are_capped_at_100_uris() {
+        let server = MockServer::start().await;
+        let client = user_client(&server, "user_token", 3600);
+        let uris: Vec<String> = (0..101).map(|i| format!("spotify:track:track{i}")).collect();
+
+        let added = client.add_items_to_playlist("playlist1", &uris, None, None).await;
+        assert!(
+            matches!(added, Err(RustyError::Io(e)) if e.kind() == std::io::ErrorKind::InvalidInput)
+        );
+        let removed = client.remove_playlist_items("playlist1", &uris, None).await;
+        assert!(
+            matches!(removed, Err(RustyError::Io(e)) if e.kind() == std::io::ErrorKind::InvalidInput)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_playback_state_is_none_when_nothing_plays() {
+        let server = MockServer::start().await;
+        let client = user_client(&server, "user_token", 3600);
+        Mock::given(method("GET"))
+            .and(path("/v1/me/player"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server).await;
+
+        assert!(client.get_playback_state(None).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_get_playback_state() {
+        let server = MockServer::start().await;
+        let client = user_client(&server, "user_token", 3600);
+        Mock::given(method("GET"))
+            .and(path("/v1/me/player"))
+            .and(query_param("market", "from_token"))
+            .and(query_param("additional_types", "track,episode"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({
+                        "device": {
+                            "id": "device1",
+                            "is_active": true,
+                            "is_private_session": false,
+                            "is_restricted": false,
+                            "name": "Kitchen speaker",
+                            "type": "Speaker",
+                            "volume_percent": 40,
+                            "supports_volume": true
+                        },
+                        "repeat_state": "off",
+                        "shuffle_state": true,
+                        "context": null,
+                        "timestamp": 1700000000000u64,
+                        "progress_ms": 42000,
+                        "is_playing": true,
+                        "item": {
+                            "type": "track",
+                            "album": {
+                                "album_type": "album",
+                                "total_tracks": 1,
+                                "external_urls": { "spotify": "https://open.spotify.com/album/album1" },
+                                "href": "https://api.spotify.com/v1/albums/album1",
+                                "id": "album1",
+                                "images": [],
+                                "name": "Album Name",
+                                "release_date": "2024-01-01",
+                                "release_date_precision": "day",
+                                "type": "album",
+                                "uri": "spotify:album:album1",
+                                "artists": []
+                            },
+                            "id": "track1",
+                            "name": "Track Name",
+                            "artists": [],
+                            "duration_ms": 200000,
+                            "preview_url": null,
+                            "external_urls": { "spotify": "https://open.spotify.com/track/track1" }
+                        },
+                        "currently_playing_type": "track"
+                    })
+                )
+            )
+            .expect(1)
+            .mount(&server).await;
+
+        let state = client.get_playback_state(Some(Market::FromToken)).await.unwrap().unwrap();
+        assert_eq!(state.device.id.as_deref(), Some("device1"));
+        assert_eq!(state.progress_ms, Some(42000));
+        assert!(matches!(state.item, Some(PlayingItem::Track(track)) if track.id == "track1"));
+    }
+
+    #[tokio::test]
+    async fn test_get_recently_played() {
+        let server = MockServer::start().await;
+        let client = user_client(&server, "user_token", 3600);
+        Mock::given(method("GET"))
+            .and(path("/v1/me/player/recently-played"))
+            .and(query_param("limit", "10"))
+            .and(query_param("before", "1700000000000"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({
+                        "href": "https://api.spotify.com/v1/me/player/recently-played?limit=10",
+                        "items": [],
+                        "limit": 10,
+                        "next": null,
+                        "cursors": { "after": "1700000000000", "before": "1690000000000" }
+                    })
+                )
+            )
+            .expect(1)
+            .mount(&server).await;
+
+        let page = client.get_recently_played(Some(10), Some(1700000000000), None).await.unwrap();
+        assert_eq!(page.cursors.unwrap().before.as_deref(), Some("1690000000000"));
+        let both = client.get_recently_played(None, Some(1), Some(2)).await;
+        assert!(
+            matches!(both, Err(RustyError::Io(e)) if e.kind() == std::io::ErrorKind::InvalidInput)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_top_tracks_and_artists() {
+        let server = MockServer::start().await;
+        let client = user_client(&server, "user_token", 3600);
+        let empty_page = serde_json::json!({
+            "href": "https://api.spotify.com/v1/me/top",
+            "items": [],
+            "limit": 5,
+            "next": null,
+            "offset": 0,
+            "previous": null,
+            "total": 0
+        });
+        Mock::given(method("GET"))
+            .and(path("/v1/me/top/tracks"))
+            .and(query_param("time_range", "short_term"))
+            .and(query_param("limit", "5"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&empty_page))
+            .expect(1)
+            .mount(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/me/top/artists"))
+            .and(query_param("time_range", "medium_term"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&empty_page))
+            .expect(1)
+            .mount(&server).await;
+
+        let tracks = client.get_top_tracks(Some(TimeRange::ShortTerm), Some(5), None).await.unwrap();
+        assert!(tracks.is_empty());
+        let artists = client.get_top_artists(None, Some(5), None).await.unwrap();
+        assert!(artists.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_follow_and_check_following() {
+        let server = MockServer::start().await;
+        let client = user_client(&server, "user_token", 3600);
+        let ids = vec!["artist1".to_string(), "artist2".to_string()];
+        Mock::given(method("PUT"))
+            .and(path("/v1/me/following"))
+            .and(query_param("type", "artist"))
+            .and(body_json(serde_json::json!({ "ids": ["artist1", "artist2"] })))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server).await;
+        Mock::given(method("DELETE"))
+            .and(path("/v1/me/following"))
+            .and(query_param("type", "user"))
+            .and(body_json(serde_json::json!({ "ids": ["smedjan"] })))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/me/following/contains"))
+            .and(query_param("type", "artist"))
+            .and(query_param("ids", "artist1,artist2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([true, false])))
+            .expect(1)
+            .mount(&server).await;
+
+        client.follow_artists(&ids).await.unwrap();
+        client.unfollow_users(&["smedjan".to_string()]).await.unwrap();
+        assert_eq!(client.check_following(FollowType::Artist, &ids).await.unwrap(), [true, false]);
+
+        let too_many: Vec<String> = (0..51).map(|i| format!("artist{i}")).collect();
+        let followed = client.follow_artists(&too_many).await;
+        assert!(
+            matches!(followed, Err(RustyError::Io(e)) if e.kind() == std::io::ErrorKind::InvalidInput)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_followed_artists() {
+        let server = MockServer::start().await;
+        let client = user_client(&server, "user_token", 3600);
+        Mock::given(method("GET"))
+            .and(path("/v1/me/following"))
+            .and(query_param("type", "artist"))
+            .and(query_param("after", "artist1"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({
+                        "artists": {
+                            "href": "https://api.spotify.com/v1/me/following?type=artist&after=artist1",
+                            "items": [],
+                            "limit": 20,
+                            "next": null,
+                            "cursors": { "after": null },
+                            "total": 1
+                        }
+                    })
+                )
+            )
+            .expect(1)
+            .mount(&server).await;
+
+        let page = client.get_followed_artists(None, Some("artist1")).await.unwrap();
+        assert_eq!(page.total, Some(1));
+        assert!(page.items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_player_commands() {
+        let server = MockServer::start().await;
+        let client = user_client(&server, "user_token", 3600);
+        Mock::given(method("PUT"))
+            .and(path("/v1/me/player/play"))
+            .and(query_param("device_id", "device1"))
+            .and(body_json(serde_json::json!({ "context_uri": "spotify:album:album1" })))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server).await;
+        Mock::given(method("PUT"))
+            .and(path("/v1/me/player/pause"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server).await;
+        Mock::given(method("POST"))
+            .and(path("/v1/me/player/next"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server).await;
+        Mock::given(method("PUT"))
+            .and(path("/v1/me/player/seek"))
+            .and(query_param("position_ms", "25000"))
+            .and(query_param("device_id", "device1"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server).await;
+        Mock::given(method("PUT"))
+            .and(path("/v1/me/player/shuffle"))
+            .and(query_param("state", "false"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server).await;
+
+        client
+            .start_resume_playback(Some("device1"), Some("spotify:album:album1"), None).await
+            .unwrap();
+        client.pause_playback(None).await.unwrap();
+        client.skip_to_next(None).await.unwrap();
+        client.seek(25000, Some("device1")).await.unwrap();
+        client.toggle_shuffle(false, None).await.unwrap();
+        let volume = client.set_volume(101, None).await;
+        assert!(
+            matches!(volume, Err(RustyError::Io(e)) if e.kind() == std::io::ErrorKind::InvalidInput)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_current_user_is_not_cached() {
+        let server = MockServer::start().await;
+        let client = user_client(&server, "user_token", 3600);
+        Mock::given(method("GET"))
+            .and(path("/v1/me"))
+            .and(header("Authorization", "Bearer user_token"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({
+                        "country": "SE",
+                        "display_name": "Lilla Namo",
+                        "email": "lilla@example.com",
+                        "explicit_content": { "filter_enabled": false, "filter_locked": false },
+                        "external_urls": { "spotify": "https://open.spotify.com/user/smedjan" },
+                        "followers": { "href": null, "total": 4561 },
+                        "href": "https://api.spotify.com/v1/users/smedjan",
+                        "id": "smedjan",
+                        "images": [],
+                        "product": "premium",
+                        "type": "user",
+                        "uri": "spotify:user:smedjan"
+                    })
+                )
+            )
+            .expect(2)
+            .mount(&server).await;
+
+        let user = client.get_current_user().await.unwrap();
+        assert_eq!(user.email.as_deref(), Some("lilla@example.com"));
+        assert_eq!(user.product.as_deref(), Some("premium"));
+        client.get_current_user().await.unwrap();
+        assert!(!client.cache_contains("/me").await);
+    }
+
+    #[tokio::test]
+    async fn test_save_tracks_sends_at_most_50_ids_per_request() {
+        let server = MockServer::start().await;
+        let client = user_client(&server, "user_token", 3600);
+        let ids: Vec<String> = (0..60).map(|i| format!("track{i}")).collect();
+        Mock::given(method("PUT"))
+            .and(path("/v1/me/tracks"))
+            .and(body_json(serde_json::json!({ "ids": ids[..50] })))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server).await;
+        Mock::given(method("PUT"))
+            .and(path("/v1/me/tracks"))
+            .and(body_json(serde_json::json!({ "ids": ids[50..] })))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server).await;
+
+        client.save_tracks(&ids).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_remove_and_check_saved_tracks() {
+        let server = MockServer::start().await;
+        let client = user_client(&server, "user_token", 3600);
+        let ids = vec!["track1".to_string(), "track2".to_string()];
+        Mock::given(method("DELETE"))
+            .and(path("/v1/me/tracks"))
+            .and(body_json(serde_json::json!({ "ids": ["track1", "track2"] })))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/me/tracks/contains"))
+            .and(query_param("ids", "track1,track2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([false, true])))
+            .expect(1)
+            .mount(&server).await;
+
+        client.remove_saved_tracks(&ids).await.unwrap();
+        assert_eq!(client.check_saved_tracks(&ids).await.unwrap(), [false, true]);
+    }
+
+    #[tokio::test]
+    async fn test_get_saved_tracks() {
+        let server = MockServer::start().await;
+        let client = user_client(&server, "user_token", 3600);
+        Mock::given(method("GET"))
+            .and(path("/v1/me/tracks"))
+            .and(query_param("limit", "50"))
+            .and(query_param("market", "from_token"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({
+                        "href": "https://api.spotify.com/v1/me/tracks",
+                        "items": [],
+                        "limit": 50,
+                        "next": null,
+                        "offset": 0,
+                        "previous": null,
+                        "total": 0
+                    })
+                )
+            )
+            .expect(1)
+            .mount(&server).await;
+
+        let page = client.get_saved_tracks(Some(100), None, Some(Market::FromToken)).await.unwrap();
+        assert!(page.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_user_client_accepts_from_token_market() {
+        let server = MockServer::start().await;
+        let client = user_client(&server, "user_token", 3600);
+        Mock::given(method("GET"))
+            .and(path("/v1/artists/artist1/top-tracks"))
+            .and(query_param("market", "from_token"))
+            .and(header("Authorization", "Bearer user_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "tracks": [] })))
+            .expect(1)
+            .mount(&server).await;
+
+        client.get_artist_top_tracks("artist1", Some(Market::FromToken)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_user_client_refreshes_expired_token() {
+        let server = MockServer::start().await;
+        let client = user_client(&server, "expired_token", 0);
+        Mock::given(method("POST"))
+            .and(path("/api/token"))
+            .and(body_string_contains("grant_type=refresh_token"))
+            .and(body_string_contains("refresh_token=refresh_token"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({
+                        "access_token": "refreshed_token",
+                        "token_type": "Bearer",
+                        "scope": "user-read-private",
+                        "expires_in": 3600
+                    })
+                )
+            )
+            .expect(1)
+            .mount(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/artists/artist1/top-tracks"))
+            .and(header("Authorization", "Bearer refreshed_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "tracks": [] })))
+            .expect(1)
+            .mount(&server).await;
+
+        client.get_artist_top_tracks("artist1", None).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_stored_user_token_is_refreshed_once_expired() {
+        let server = MockServer::start().await;
+        let mut client = SpotifyUserClient::from_user_token(
+            "client_id".to_string(),
+            "stored_token".to_string(),
+            Some("stored_refresh_token".to_string()),
+            0
+        );
+        client.use_mock_server(&server.uri());
+        Mock::given(method("POST"))
+            .and(path("/api/token"))
+            .and(body_string_contains("refresh_token=stored_refresh_token"))
+            .and(body_string_contains("client_id=client_id"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({
+                        "access_token": "refreshed_token",
+                        "token_type": "Bearer",
+                        "scope": "user-read-private",
+                        "expires_in": 3600
+                    })
+                )
+            )
+            .expect(1)
+            .mount(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/artists/artist1/top-tracks"))
+            .and(header("Authorization", "Bearer refreshed_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "tracks": [] })))
+            .expect(1)
+            .mount(&server).await;
+
+        client.get_artist_top_tracks("artist1", None).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_stored_user_token_is_used_until_it_expires() {
+        let server = MockServer::start().await;
+        let expires_at = AccessTokenResponse {
+            access_token: "stored_token".to_string(),
+            token_type: "Bearer".to_string(),
+            scope: "user-read-private".to_string(),
+            expires_in: 3600,
+            refresh_token: None,
+        }.expires_at_unix();
+        let mut client = SpotifyUserClient::from_user_token(
+            "client_id".to_string(),
+            "stored_token".to_string(),
+            None,
+            expires_at
+        );
+        client.use_mock_server(&server.uri());
+        Mock::given(method("GET"))
+            .and(path("/v1/artists/artist1/top-tracks"))
+            .and(header("Authorization", "Bearer stored_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "tracks": [] })))
+            .expect(1)
+            .mount(&server).await;
+
+        client.get_artist_top_tracks("artist1", None).await.unwrap();
+    }
+}